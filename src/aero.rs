@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{DragCoefficients, ProjectileInput};
+
+/// Geometry-derived aerodynamic coefficients needed by the modified
+/// point-mass trajectory. Slopes are per radian, the center of pressure is
+/// in calibers from the nose, and CMa is referenced to the center of
+/// gravity (positive = overturning). `cd_delta2` is the yaw drag
+/// coefficient and `cm_q` the pitch damping sum CMq + CMa-dot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AeroCoefficients {
+    pub mach: f64,
+    pub cd0: f64,
+    pub cn_alpha: f64,
+    pub cl_alpha: f64,
+    pub cm_alpha: f64,
+    pub clp: f64,
+    pub cd_delta2: f64,
+    pub cm_q: f64,
+    pub x_cp: f64,
+}
+
+impl ProjectileInput {
+    /// Normal-force slope and center of pressure of the nose, cylinder and
+    /// boattail, estimated with slender-body theory. The cylinder carries a
+    /// small viscous lift increment and the boattail only half of its
+    /// slender-body lift loss, which matches spark-range data for spitzer
+    /// bullets better than the inviscid values.
+    fn normal_force_components(&self) -> [(f64, f64); 3] {
+        let nose = (2.0, self.nose_length * (0.667 - 0.2 * self.rt_r.clamp(0.0, 1.0)));
+
+        let cylinder_length = (self.total_length - self.nose_length - self.boattail_length).max(0.0);
+        let cylinder = (0.4 * cylinder_length, self.nose_length + 0.5 * cylinder_length);
+
+        let boattail = if self.boattail_length > 0.0 {
+            (
+                self.base_diameter * self.base_diameter - 1.0,
+                self.total_length - 0.5 * self.boattail_length,
+            )
+        } else {
+            (0.0, self.total_length)
+        };
+
+        [nose, cylinder, boattail]
+    }
+
+    /// Aerodynamic coefficients for each Mach number of a drag table, about
+    /// the center of gravity `cg` (calibers from the nose). Roll damping is
+    /// taken from the skin friction drag, Clp = -CDSF / 4, which follows from
+    /// the wall shear acting at one body radius. Resolving normal and axial
+    /// force into the wind axes gives the yaw drag CDd2 = CNa - CD0 / 2, and
+    /// quasi-steady slender-body theory the pitch damping
+    /// CMq + CMa-dot = -2 * sum(CNa_i * (x_i - cg)^2). A sphere has no
+    /// lift, overturning moment or yaw drag.
+    pub fn aero_coefficients(&self, drag: &[DragCoefficients], cg: f64) -> Vec<AeroCoefficients> {
+        if self.is_sphere() {
+            return drag.iter()
+                .map(|row| AeroCoefficients {
+                    mach: row.mach,
+                    cd0: row.cd0,
+                    cn_alpha: 0.0,
+                    cl_alpha: 0.0,
+                    cm_alpha: 0.0,
+                    clp: -0.25 * row.cdsf,
+                    cd_delta2: 0.0,
+                    cm_q: 0.0,
+                    x_cp: cg,
+                })
+                .collect();
+        }
+
+        let components = self.normal_force_components();
+        let cn_alpha: f64 = components.iter().map(|(cn, _)| cn).sum();
+        let moment: f64 = components.iter().map(|(cn, x)| cn * (cg - x)).sum();
+        let x_cp = cg - moment / cn_alpha;
+        let cm_q: f64 = -2.0 * components.iter().map(|(cn, x)| cn * (x - cg) * (x - cg)).sum::<f64>();
+
+        drag.iter()
+            .map(|row| AeroCoefficients {
+                mach: row.mach,
+                cd0: row.cd0,
+                cn_alpha,
+                cl_alpha: cn_alpha - row.cd0,
+                cm_alpha: moment,
+                clp: -0.25 * row.cdsf,
+                cd_delta2: cn_alpha - 0.5 * row.cd0,
+                cm_q,
+                x_cp,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+const GAS_CONSTANT_DRY: f64 = 287.058;
+const GAS_CONSTANT_VAPOR: f64 = 461.495;
+const GAMMA: f64 = 1.4;
+
+/// Firing-site atmosphere. Temperature in degrees C, pressure in hPa
+/// (station pressure, not corrected to sea level) and relative humidity
+/// as a fraction between 0 and 1.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Atmosphere {
+    pub temperature: f64,
+    pub pressure: f64,
+    pub humidity: f64,
+}
+
+impl Default for Atmosphere {
+    /// ICAO standard sea-level atmosphere, dry air.
+    fn default() -> Self {
+        Atmosphere {
+            temperature: 15.0,
+            pressure: 1013.25,
+            humidity: 0.0,
+        }
+    }
+}
+
+impl Atmosphere {
+    fn kelvin(&self) -> f64 {
+        self.temperature + 273.15
+    }
+
+    /// Saturation vapor pressure (hPa) from the Tetens formula.
+    fn saturation_pressure(&self) -> f64 {
+        6.1078 * 10f64.powf(7.5 * self.temperature / (self.temperature + 237.3))
+    }
+
+    /// Air density in kg/m^3, treating moist air as a mix of dry air and vapor.
+    pub fn density(&self) -> f64 {
+        let vapor = self.humidity.clamp(0.0, 1.0) * self.saturation_pressure();
+        let dry = self.pressure - vapor;
+        (dry * 100.0) / (GAS_CONSTANT_DRY * self.kelvin())
+            + (vapor * 100.0) / (GAS_CONSTANT_VAPOR * self.kelvin())
+    }
+
+    /// Speed of sound in m/s.
+    pub fn speed_of_sound(&self) -> f64 {
+        (GAMMA * GAS_CONSTANT_DRY * self.kelvin()).sqrt()
+    }
+}
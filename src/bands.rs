@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectileInput;
+
+/// Width (calibers) of the band McCoy's band drag term was fitted to.
+const REFERENCE_WIDTH: f64 = 0.2;
+
+/// Share of band drag carried by the two faces of the band; the rest is
+/// friction on the crown and grows with the width.
+const STEP_SHARE: f64 = 0.85;
+
+/// Length of the separated flow behind a band, in band heights. A band
+/// starting inside it is partly shielded by the one ahead.
+const REATTACHMENT_HEIGHTS: f64 = 6.0;
+
+/// Kind of raised ring on the body, which sets how sharply its front face
+/// meets the flow.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BandKind {
+    Rotating,
+    Obturator,
+    Bourrelet,
+}
+
+impl BandKind {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_uppercase().as_str() {
+            "R" => Some(BandKind::Rotating),
+            "O" => Some(BandKind::Obturator),
+            "B" => Some(BandKind::Bourrelet),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            BandKind::Rotating => "ROTATING",
+            BandKind::Obturator => "OBTURATOR",
+            BandKind::Bourrelet => "BOURRELET",
+        }
+    }
+
+    /// Drag of this kind of band relative to a square-edged rotating band
+    /// of the same height: the rounded edges of a plastic obturator and the
+    /// tapered shoulder of a bourrelet turn the flow more gently.
+    fn edge_factor(&self) -> f64 {
+        match self {
+            BandKind::Rotating => 1.0,
+            BandKind::Obturator => 0.8,
+            BandKind::Bourrelet => 0.5,
+        }
+    }
+}
+
+/// Raised ring on the body: start and width in calibers from the nose tip,
+/// outer diameter in calibers.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Band {
+    pub kind: BandKind,
+    pub location: f64,
+    pub width: f64,
+    pub diameter: f64,
+}
+
+/// Band drag of one Mach row and the share of each band, in the order the
+/// bands were given.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandDrag {
+    pub mach: f64,
+    pub cdbnd: f64,
+    pub bands: Vec<f64>,
+}
+
+/// McCoy's band drag per caliber of band diameter above the body at `mach`.
+pub(crate) fn band_factor(mach: f64) -> f64 {
+    if mach < 0.95 {
+        mach.powf(12.5)
+    } else {
+        0.21 + 0.28 / (mach * mach)
+    }
+}
+
+impl ProjectileInput {
+    /// Whether every band lies on the cylinder, clear of the others.
+    pub fn bands_fit(&self) -> bool {
+        let boattail_start = self.total_length - self.boattail_length;
+        let mut bands: Vec<&Band> = self.bands.iter().collect();
+        bands.sort_by(|a, b| a.location.total_cmp(&b.location));
+        bands.iter().all(|band| {
+            band.width > 0.0
+                && band.diameter >= 1.0
+                && band.location >= self.nose_length
+                && band.location + band.width <= boattail_start
+        }) && bands.windows(2).all(|pair| pair[0].location + pair[0].width <= pair[1].location)
+    }
+
+    /// CDBND at `mach`: McCoy's single band of `band_diameter`, or the sum
+    /// over `bands` when they are given.
+    pub fn band_drag_at(&self, mach: f64) -> f64 {
+        if self.bands.is_empty() {
+            band_factor(mach) * (self.band_diameter - 1.0)
+        } else {
+            self.band_drag_breakdown_at(mach).cdbnd
+        }
+    }
+
+    /// Band drag split between the bands. Each band carries McCoy's term
+    /// for its height, scaled by its edge factor and by its width against
+    /// the 0.2 caliber band of the correlation. A band whose front face
+    /// lies in the separated flow behind the band ahead has the height it
+    /// shares with that band shielded, in proportion to how far short of
+    /// reattachment it starts.
+    pub fn band_drag_breakdown_at(&self, mach: f64) -> BandDrag {
+        let factor = band_factor(mach);
+        let bands: Vec<f64> = self
+            .bands
+            .iter()
+            .map(|band| {
+                let height = 0.5 * (band.diameter - 1.0);
+                let ahead = self
+                    .bands
+                    .iter()
+                    .filter(|other| other.location + other.width <= band.location && other.diameter > 1.0)
+                    .max_by(|a, b| a.location.total_cmp(&b.location));
+                let exposed = match ahead {
+                    Some(other) => {
+                        let other_height = 0.5 * (other.diameter - 1.0);
+                        let gap = band.location - other.location - other.width;
+                        let shielding = 1.0 - (gap / (REATTACHMENT_HEIGHTS * other_height)).min(1.0);
+                        height - height.min(other_height) * shielding
+                    }
+                    None => height,
+                };
+                let width = STEP_SHARE + (1.0 - STEP_SHARE) * band.width / REFERENCE_WIDTH;
+                factor * 2.0 * exposed.max(0.0) * band.kind.edge_factor() * width
+            })
+            .collect();
+        BandDrag {
+            mach,
+            cdbnd: bands.iter().sum(),
+            bands,
+        }
+    }
+
+    /// Band drag breakdown for each Mach number of the drag table.
+    pub fn band_drag_breakdown(&self) -> Vec<BandDrag> {
+        self.calculate_drag_coefficients()
+            .iter()
+            .map(|row| self.band_drag_breakdown_at(row.mach))
+            .collect()
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::trajectory::interpolate;
+use crate::{DragCoefficients, ProjectileInput};
+
+/// Largest share of base drag mass injection can relieve, and the
+/// injection parameter over which the relief builds up exponentially
+/// towards it.
+const MAX_RELIEF: f64 = 0.7;
+const INJECTION_SCALE: f64 = 0.002;
+
+/// Injection parameter beyond which the bleed jet punches through the wake
+/// instead of filling it, where the model no longer holds.
+const MAX_INJECTION: f64 = 0.01;
+
+/// Base bleed unit or burning tracer. `injection` is the injection
+/// parameter I, the bleed mass flow over the freestream mass flow through
+/// the base area; `burn_time` is in seconds, 0 for a bleed lasting the
+/// whole flight.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BaseBleed {
+    pub injection: f64,
+    #[serde(default)]
+    pub burn_time: f64,
+}
+
+impl ProjectileInput {
+    /// Share of base drag the bleed relieves while it burns. It fills the
+    /// wake with low-speed gas and so replaces any base cavity relief.
+    pub fn bleed_relief(&self) -> f64 {
+        self.base_bleed
+            .map_or(0.0, |bleed| MAX_RELIEF * (1.0 - (-bleed.injection.max(0.0) / INJECTION_SCALE).exp()))
+    }
+
+    /// Drag table with the bleed burned out or the rocket motor off.
+    pub fn power_off_drag_coefficients(&self) -> Vec<DragCoefficients> {
+        self.drag_coefficients(false)
+    }
+
+    /// Start and end (seconds) of powered flight: the motor burn of a
+    /// rocket-assisted projectile, or the bleed burn from launch. `None`
+    /// when nothing burns out, including a bleed lasting the flight.
+    pub fn powered_window(&self) -> Option<(f64, f64)> {
+        match (self.rocket_motor, self.base_bleed) {
+            (Some(motor), _) => Some((motor.ignition_time, motor.ignition_time + motor.burn_time)),
+            (None, Some(bleed)) if bleed.burn_time > 0.0 => Some((0.0, bleed.burn_time)),
+            _ => None,
+        }
+    }
+
+    /// Powered window and power-off version of `drag`, a power-on table
+    /// that may have been trued: each row has its base drag moved by the
+    /// difference between power-off and power-on base drag at its Mach
+    /// number.
+    pub fn power_off_drag(&self, drag: &[DragCoefficients]) -> Option<((f64, f64), Vec<DragCoefficients>)> {
+        let window = self.powered_window()?;
+        let on = self.calculate_drag_coefficients();
+        let off = self.power_off_drag_coefficients();
+        let rows = drag
+            .iter()
+            .map(|row| {
+                let cdb = |table: &[DragCoefficients]| interpolate(table, row.mach, |r| r.mach, |r| r.cdb);
+                let delta = cdb(&off) - cdb(&on);
+                DragCoefficients {
+                    cd0: row.cd0 + delta,
+                    cdb: row.cdb + delta,
+                    pb_pinf: interpolate(&off, row.mach, |r| r.mach, |r| r.pb_pinf),
+                    ..row.clone()
+                }
+            })
+            .collect();
+        Some((window, rows))
+    }
+
+    /// Warns when the injection is past the range the model covers.
+    pub fn bleed_diagnostics(&self) -> Vec<String> {
+        let mut diagnostics = Vec::new();
+        if let Some(bleed) = self.base_bleed {
+            if bleed.injection > MAX_INJECTION {
+                diagnostics.push(format!(
+                    "INJECTION PARAMETER ABOVE {}. BLEED JET MAY PENETRATE THE WAKE. CDB MAY BE INCORRECT.",
+                    MAX_INJECTION
+                ));
+            }
+        }
+        diagnostics
+    }
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectileInput;
+
+/// Share of base drag a full-width, deep base cavity relieves below Mach
+/// 0.9 and above Mach 1.5, varying linearly in between. The vortex trapped
+/// in the cavity steadies the near wake; supersonic the gain is small.
+const SUBSONIC_RELIEF: f64 = 0.15;
+const SUPERSONIC_RELIEF: f64 = 0.03;
+
+/// Depth-to-diameter ratio at which a base cavity gives its full relief.
+const SATURATED_DEPTH: f64 = 0.5;
+
+/// Diameter of a cupped base as a fraction of the base diameter, the rest
+/// being the rim.
+const CUP_DIAMETER: f64 = 0.8;
+
+/// Depth (calibers) a tracer cavity is drawn and modelled to. The drag
+/// takes it as deep, but a drawing needs a bottom; a burned-out tracer
+/// cavity is about this deep.
+const TRACER_DRAWN_DEPTH: f64 = 1.5;
+
+/// Angle (degrees) the flow follows around a radiused base edge before it
+/// separates.
+const EDGE_SEPARATION_ANGLE: f64 = 45.0;
+
+/// Base features MCDRAG's flat base leaves out, in calibers: the depth of a
+/// cupped base, a hollow base cavity, a tracer cavity (taken burned out and
+/// deep) and the radius of the base edge. Zero omits a feature; a base has
+/// at most one of the three cavities.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BaseShape {
+    #[serde(default)]
+    pub cup_depth: f64,
+    #[serde(default)]
+    pub hollow_diameter: f64,
+    #[serde(default)]
+    pub hollow_depth: f64,
+    #[serde(default)]
+    pub tracer_diameter: f64,
+    #[serde(default)]
+    pub edge_radius: f64,
+}
+
+impl BaseShape {
+    /// Diameter and depth of the base cavity, if there is one.
+    pub fn cavity(&self, base_diameter: f64) -> Option<(f64, f64)> {
+        if self.cup_depth > 0.0 {
+            Some((CUP_DIAMETER * base_diameter, self.cup_depth))
+        } else if self.hollow_diameter > 0.0 {
+            Some((self.hollow_diameter, self.hollow_depth))
+        } else if self.tracer_diameter > 0.0 {
+            Some((self.tracer_diameter, f64::INFINITY))
+        } else {
+            None
+        }
+    }
+
+    /// Diameter and depth of the base cavity as drawn and modelled, with a
+    /// tracer cavity `TRACER_DRAWN_DEPTH` deep.
+    pub fn drawn_cavity(&self, base_diameter: f64) -> Option<(f64, f64)> {
+        self.cavity(base_diameter)
+            .map(|(diameter, depth)| (diameter, depth.min(TRACER_DRAWN_DEPTH)))
+    }
+
+    /// Whether the features describe one cavity inside the base and an edge
+    /// radius that leaves a flat rim.
+    pub fn fits(&self, base_diameter: f64) -> bool {
+        let cavities = [self.cup_depth, self.hollow_diameter, self.tracer_diameter]
+            .iter()
+            .filter(|&&value| value > 0.0)
+            .count();
+        let open = self.cavity(base_diameter).map_or(0.0, |(diameter, _)| diameter);
+        cavities <= 1
+            && (self.hollow_diameter <= 0.0 || self.hollow_depth > 0.0)
+            && open < base_diameter
+            && self.edge_radius >= 0.0
+            && open + 2.0 * self.edge_radius <= base_diameter
+    }
+}
+
+impl ProjectileInput {
+    /// Fraction of the base drag a base cavity relieves at `mach`, scaled
+    /// by the share of the base it opens and by its depth up to half its
+    /// diameter.
+    pub fn base_cavity_relief(&self, mach: f64) -> f64 {
+        let base_diameter = self.base_diameter;
+        let Some((diameter, depth)) = self.base_shape.and_then(|shape| shape.cavity(base_diameter)) else {
+            return 0.0;
+        };
+        let relief = if mach <= 0.9 {
+            SUBSONIC_RELIEF
+        } else if mach >= 1.5 {
+            SUPERSONIC_RELIEF
+        } else {
+            SUBSONIC_RELIEF + (SUPERSONIC_RELIEF - SUBSONIC_RELIEF) * (mach - 0.9) / 0.6
+        };
+        let area = (diameter / base_diameter).powi(2).min(1.0);
+        relief * area * (depth / (SATURATED_DEPTH * diameter)).min(1.0)
+    }
+
+    /// Base diameter the wake sees. With a radiused edge the flow stays on
+    /// the radius to the separation angle, shrinking the base.
+    pub fn effective_base_diameter(&self) -> f64 {
+        let radius = self.base_shape.map_or(0.0, |shape| shape.edge_radius.max(0.0));
+        self.base_diameter - 2.0 * radius * (1.0 - EDGE_SEPARATION_ANGLE.to_radians().cos())
+    }
+
+    /// McCoy's base pressure ratio `pb_pinf` raised by the base cavity
+    /// relief at `mach`, or by the bleed relief while a base bleed is
+    /// `burning`.
+    pub fn base_pressure_at(&self, mach: f64, pb_pinf: f64, burning: bool) -> f64 {
+        let relief = if burning && self.base_bleed.is_some() {
+            self.bleed_relief()
+        } else {
+            self.base_cavity_relief(mach)
+        };
+        1.0 - (1.0 - pb_pinf) * (1.0 - relief)
+    }
+}
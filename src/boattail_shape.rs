@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectileInput;
+
+/// Share of a free base's pressure deficit carried by the face of a rebate
+/// step. The shear layer off the step reattaches on the cone within a few
+/// step heights, so the face sits in a short, partly relieved recirculation.
+const STEP_SHARE: f64 = 0.5;
+
+/// Shaped boattail in calibers: the radius of a transition arc blending the
+/// cylinder into the cone, and the depth of a rebate step down to the start
+/// of the cone. Zero omits either; both together give a rebate followed by a
+/// radiused cone.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BoattailShape {
+    #[serde(default)]
+    pub transition_radius: f64,
+    #[serde(default)]
+    pub rebate_depth: f64,
+}
+
+impl ProjectileInput {
+    /// Radius (calibers) of the body just behind any rebate step, where the
+    /// boattail proper starts.
+    pub(crate) fn boattail_shoulder(&self) -> f64 {
+        0.5 - self.boattail_shape.map_or(0.0, |shape| shape.rebate_depth)
+    }
+
+    /// Half-angle (radians) of the straight part of a radiused boattail,
+    /// and whether the arc fits. The arc and the cone together have to fall
+    /// from the shoulder to the base over the boattail length; when even
+    /// the steepest arc that fits the length falls short, that arc's angle
+    /// is returned.
+    fn boattail_cone_angle(&self, radius: f64) -> (f64, bool) {
+        let drop = self.boattail_shoulder() - 0.5 * self.base_diameter;
+        let fall = |angle: f64| self.boattail_length * angle.tan() - radius * (1.0 / angle.cos() - 1.0) - drop;
+        let mut high = if radius > self.boattail_length {
+            (self.boattail_length / radius).asin()
+        } else {
+            std::f64::consts::FRAC_PI_2 - 1e-6
+        };
+        if fall(high) < 0.0 {
+            return (high, false);
+        }
+        let mut low = 0.0;
+        for _ in 0..60 {
+            let mid = 0.5 * (low + high);
+            if fall(mid) < 0.0 {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (0.5 * (low + high), true)
+    }
+
+    /// Length and slope of the straight cone McCoy's boattail equations are
+    /// given. For a shaped boattail this is the cone of the straight part,
+    /// extended forward to the shoulder radius; a transition arc makes it
+    /// shorter and steeper than the boattail inputs, a rebate shallower.
+    pub fn boattail_cone(&self) -> (f64, f64) {
+        if self.boattail_length <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let drop = self.boattail_shoulder() - 0.5 * self.base_diameter;
+        match self.boattail_shape {
+            Some(shape) if shape.transition_radius > 0.0 => {
+                let slope = self.boattail_cone_angle(shape.transition_radius).0.tan();
+                (drop / slope, slope)
+            }
+            _ => (self.boattail_length, drop / self.boattail_length),
+        }
+    }
+
+    /// Effective boattail half-angle in degrees: that of the straight part
+    /// of the boattail, which the flow follows to the base.
+    pub fn effective_boattail_angle(&self) -> f64 {
+        self.boattail_cone().1.atan().to_degrees()
+    }
+
+    /// Diameter reduction (calibers) in McCoy's base pressure boattail term:
+    /// that along the cone, as the flow separates at a rebate step rather
+    /// than turning.
+    pub fn boattail_convergence(&self) -> f64 {
+        match self.boattail_shape {
+            Some(_) if self.boattail_length > 0.0 => 2.0 * self.boattail_shoulder() - self.base_diameter,
+            _ => 1.0 - self.base_diameter,
+        }
+    }
+
+    /// Drag of the rebate step face at `mach`, given McCoy's flat base
+    /// pressure ratio `pb_pinf`. Added to CDBT.
+    pub fn rebate_drag_at(&self, mach: f64, pb_pinf: f64) -> f64 {
+        if self.boattail_length <= 0.0 {
+            return 0.0;
+        }
+        let shoulder = 2.0 * self.boattail_shoulder();
+        STEP_SHARE * 1.4286 * (1.0 - pb_pinf) / (mach * mach) * (1.0 - shoulder * shoulder)
+    }
+
+    /// Transition arc of a radiused boattail in calibers: centre (x, r)
+    /// from the nose tip, radius, and the angle (radians) the arc turns
+    /// through before it meets the cone. `None` without an arc.
+    pub fn boattail_arc(&self) -> Option<((f64, f64), f64, f64)> {
+        let radius = self.boattail_shape.map_or(0.0, |shape| shape.transition_radius);
+        if self.boattail_length <= 0.0 || radius <= 0.0 {
+            return None;
+        }
+        let (angle, _) = self.boattail_cone_angle(radius);
+        let start = self.total_length - self.boattail_length;
+        Some(((start, self.boattail_shoulder() - radius), radius, angle))
+    }
+
+    /// Body radius `x` calibers aft of the start of the boattail: the
+    /// rebate step, then the transition arc tangent to the shoulder, then
+    /// the cone.
+    pub fn boattail_radius_at(&self, x: f64) -> f64 {
+        let shoulder = self.boattail_shoulder();
+        match self.boattail_shape {
+            Some(shape) if shape.transition_radius > 0.0 => {
+                let radius = shape.transition_radius;
+                let (angle, _) = self.boattail_cone_angle(radius);
+                let arc = radius * angle.sin();
+                if x <= arc {
+                    shoulder - (radius - (radius * radius - x * x).sqrt())
+                } else {
+                    shoulder - radius * (1.0 - angle.cos()) - (x - arc) * angle.tan()
+                }
+            }
+            _ => shoulder + (0.5 * self.base_diameter - shoulder) * x / self.boattail_length,
+        }
+    }
+
+    /// Whether the shape has a boattail to belong to, the rebate leaves a
+    /// cone that still narrows to the base and the arc fits the boattail
+    /// length.
+    pub fn boattail_shape_fits(&self) -> bool {
+        let Some(shape) = self.boattail_shape else {
+            return true;
+        };
+        let drop = self.boattail_shoulder() - 0.5 * self.base_diameter;
+        let arc_fits = shape.transition_radius <= 0.0 || self.boattail_cone_angle(shape.transition_radius).1;
+        self.boattail_length > 0.0
+            && shape.transition_radius >= 0.0
+            && shape.rebate_depth >= 0.0
+            && drop > 0.0
+            && arc_fits
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{head_drag, ProjectileInput};
+
+/// Two-segment nose: a tip segment (a fuze cone, or a tip ogive with its
+/// own RT/R) from the meplat to `tip_length` calibers, where its diameter is
+/// `tip_diameter`, ahead of the main ogive, which runs on to the nose length
+/// with the input RT/R. `tip_rt_r` of 0 makes the tip a cone.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CompoundNose {
+    pub tip_length: f64,
+    pub tip_diameter: f64,
+    #[serde(default)]
+    pub tip_rt_r: f64,
+}
+
+/// Head drag of one Mach row split between the nose segments. A simple
+/// nose puts all of it on the main ogive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadDragSplit {
+    pub mach: f64,
+    pub cdh: f64,
+    pub cdh_tip: f64,
+    pub cdh_ogive: f64,
+}
+
+impl CompoundNose {
+    /// Whether the tip segment fits inside the nose it belongs to.
+    pub fn fits(&self, nose_length: f64, meplat_diameter: f64) -> bool {
+        self.tip_length > 0.0
+            && self.tip_length < nose_length
+            && self.tip_diameter > meplat_diameter
+            && self.tip_diameter < 1.0
+            && (0.0..=1.0).contains(&self.tip_rt_r)
+    }
+}
+
+impl ProjectileInput {
+    /// CDH at `mach`, from the two-segment model for a compound nose.
+    pub fn head_drag_at(&self, mach: f64) -> f64 {
+        let split = self.head_drag_split_at(mach);
+        split.cdh_tip + split.cdh_ogive
+    }
+
+    /// Two-segment head drag. The tip is treated as a nose of its own,
+    /// scaled to its base diameter, so its CDH (meplat face included) is
+    /// carried over in proportion to its base area. The main ogive is a
+    /// nose whose meplat is the tip base; only its wave drag is kept, since
+    /// that face is covered by the tip. A rounded tip scales the meplat face
+    /// drag by its face factor.
+    pub fn head_drag_split_at(&self, mach: f64) -> HeadDragSplit {
+        let face_factor = self.meplat_face_factor();
+        let (cdh_tip, cdh_ogive) = match self.compound_nose {
+            Some(tip) => {
+                let k = tip.tip_diameter;
+                let (tip_wave, tip_face) = head_drag(mach, tip.tip_length / k, self.meplat_diameter / k, tip.tip_rt_r);
+                let (ogive_wave, _) = head_drag(mach, self.nose_length - tip.tip_length, k, self.rt_r);
+                (k * k * (tip_wave + face_factor * tip_face), ogive_wave)
+            }
+            None => {
+                let (wave, face) = head_drag(mach, self.nose_length, self.meplat_diameter, self.rt_r);
+                (0.0, wave + face_factor * face)
+            }
+        };
+        HeadDragSplit {
+            mach,
+            cdh: cdh_tip + cdh_ogive,
+            cdh_tip,
+            cdh_ogive,
+        }
+    }
+
+    /// Head drag split for each Mach number of the drag table.
+    pub fn head_drag_split(&self) -> Vec<HeadDragSplit> {
+        self.calculate_drag_coefficients()
+            .iter()
+            .map(|row| self.head_drag_split_at(row.mach))
+            .collect()
+    }
+}
@@ -0,0 +1,912 @@
+use serde::{Deserialize, Serialize};
+
+use crate::bands::{Band, BandKind};
+use crate::nose_tip::NoseTip;
+use crate::ProjectileInput;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DrawingFormat {
+    Svg,
+    Dxf,
+}
+
+/// Axial position of the rotating band, which MCDRAG itself does not need:
+/// start of the band and its width, in calibers from the nose tip. Bands
+/// given by position in `bands` are drawn instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandPosition {
+    pub location: f64,
+    pub width: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrawingRequest {
+    pub format: DrawingFormat,
+    #[serde(default)]
+    pub band: Option<BandPosition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Layer {
+    Outline,
+    Center,
+    Dimension,
+}
+
+impl Layer {
+    fn name(&self) -> &str {
+        match self {
+            Layer::Outline => "OUTLINE",
+            Layer::Center => "CENTER",
+            Layer::Dimension => "DIMENSIONS",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Drawing primitives in mm with the axis along x and radius up. Arcs run
+/// counterclockwise from `start` to `end` (degrees).
+#[derive(Debug, Clone)]
+enum Entity {
+    Line { layer: Layer, from: (f64, f64), to: (f64, f64) },
+    Arc { layer: Layer, center: (f64, f64), radius: f64, start: f64, end: f64 },
+    Text { position: (f64, f64), height: f64, align: Align, text: String },
+}
+
+/// Dimensioned half-section drawing of the body contour, in mm.
+#[derive(Debug, Clone)]
+pub struct Drawing {
+    diameter: f64,
+    entities: Vec<Entity>,
+}
+
+impl Drawing {
+    fn line(&mut self, layer: Layer, from: (f64, f64), to: (f64, f64)) {
+        self.entities.push(Entity::Line { layer, from, to });
+    }
+
+    fn text(&mut self, position: (f64, f64), align: Align, text: String) {
+        let height = 0.12 * self.diameter;
+        self.entities.push(Entity::Text { position, height, align, text });
+    }
+
+    /// Outline segment mirrored about the axis.
+    fn outline(&mut self, from: (f64, f64), to: (f64, f64)) {
+        self.line(Layer::Outline, from, to);
+        self.line(Layer::Outline, (from.0, -from.1), (to.0, -to.1));
+    }
+
+    /// Length dimension between `x1` and `x2`, with extension lines from the
+    /// contour heights `y1` and `y2` to the dimension line at `y`.
+    fn horizontal_dimension(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64), y: f64, text: String) {
+        let gap = 0.05 * self.diameter;
+        for &(x, from) in &[(x1, y1), (x2, y2)] {
+            let direction = (y - from).signum();
+            self.line(Layer::Dimension, (x, from + direction * gap), (x, y + direction * gap));
+        }
+        self.line(Layer::Dimension, (x1, y), (x2, y));
+        self.ticks((x1, y), (x2, y));
+        self.text((0.5 * (x1 + x2), y + gap), Align::Center, text);
+    }
+
+    /// Diameter dimension of the section of radius `r` at `x`, drawn at `at`.
+    fn diameter_dimension(&mut self, x: f64, r: f64, at: f64, text: String) {
+        let gap = 0.05 * self.diameter;
+        let direction = (at - x).signum();
+        for &y in &[r, -r] {
+            self.line(Layer::Dimension, (x + direction * gap, y), (at + direction * gap, y));
+        }
+        self.line(Layer::Dimension, (at, -r), (at, r));
+        self.ticks((at, -r), (at, r));
+        let align = if direction > 0.0 { Align::Left } else { Align::Right };
+        self.text((at + direction * 2.0 * gap, -0.5 * gap), align, text);
+    }
+
+    /// Oblique terminator ticks at both ends of a dimension line.
+    fn ticks(&mut self, a: (f64, f64), b: (f64, f64)) {
+        let size = 0.04 * self.diameter;
+        for &(x, y) in &[a, b] {
+            self.line(Layer::Dimension, (x - size, y - size), (x + size, y + size));
+        }
+    }
+
+    fn extents(&self) -> (f64, f64, f64, f64) {
+        let mut points = Vec::new();
+        for entity in &self.entities {
+            match entity {
+                Entity::Line { from, to, .. } => {
+                    points.push(*from);
+                    points.push(*to);
+                }
+                Entity::Arc { center, radius, start, end, .. } => {
+                    for angle in [start, end] {
+                        let a = angle.to_radians();
+                        points.push((center.0 + radius * a.cos(), center.1 + radius * a.sin()));
+                    }
+                }
+                Entity::Text { position, height, align, text } => {
+                    let width = 0.6 * height * text.len() as f64;
+                    let left = match align {
+                        Align::Left => position.0,
+                        Align::Center => position.0 - 0.5 * width,
+                        Align::Right => position.0 - width,
+                    };
+                    points.push((left, position.1));
+                    points.push((left + width, position.1 + height));
+                }
+            }
+        }
+        points.iter().fold(
+            (f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            |(x0, y0, x1, y1), &(x, y)| (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+        )
+    }
+
+    pub fn format(&self, format: DrawingFormat) -> String {
+        match format {
+            DrawingFormat::Svg => self.to_svg(),
+            DrawingFormat::Dxf => self.to_dxf(),
+        }
+    }
+
+    /// SVG with a viewBox in mm, y flipped so the body reads the usual way.
+    pub fn to_svg(&self) -> String {
+        let margin = 0.25 * self.diameter;
+        let (x0, y0, x1, y1) = self.extents();
+        let width = x1 - x0 + 2.0 * margin;
+        let height = y1 - y0 + 2.0 * margin;
+        let thin = 0.01 * self.diameter;
+
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{:.4} {:.4} {:.4} {:.4}\" width=\"1000\" height=\"{:.0}\">\n",
+            x0 - margin,
+            -y1 - margin,
+            width,
+            height,
+            1000.0 * height / width
+        ));
+        out.push_str("<rect x=\"-100000\" y=\"-100000\" width=\"200000\" height=\"200000\" fill=\"white\"/>\n");
+        for entity in &self.entities {
+            match entity {
+                Entity::Line { layer, from, to } => {
+                    out.push_str(&format!(
+                        "<line x1=\"{:.4}\" y1=\"{:.4}\" x2=\"{:.4}\" y2=\"{:.4}\" {}/>\n",
+                        from.0, -from.1, to.0, -to.1, self.svg_style(*layer)
+                    ));
+                }
+                Entity::Arc { layer, center, radius, start, end } => {
+                    let (s, e) = (start.to_radians(), end.to_radians());
+                    let large = (end - start).rem_euclid(360.0) > 180.0;
+                    // Counterclockwise with y up is sweep-flag 0 once y is flipped.
+                    out.push_str(&format!(
+                        "<path d=\"M {:.4} {:.4} A {:.4} {:.4} 0 {} 0 {:.4} {:.4}\" fill=\"none\" {}/>\n",
+                        center.0 + radius * s.cos(),
+                        -(center.1 + radius * s.sin()),
+                        radius,
+                        radius,
+                        large as u8,
+                        center.0 + radius * e.cos(),
+                        -(center.1 + radius * e.sin()),
+                        self.svg_style(*layer)
+                    ));
+                }
+                Entity::Text { position, height, align, text } => {
+                    let anchor = match align {
+                        Align::Left => "start",
+                        Align::Center => "middle",
+                        Align::Right => "end",
+                    };
+                    out.push_str(&format!(
+                        "<text x=\"{:.4}\" y=\"{:.4}\" font-family=\"monospace\" font-size=\"{:.4}\" text-anchor=\"{}\" stroke-width=\"{:.4}\">{}</text>\n",
+                        position.0,
+                        -position.1,
+                        height,
+                        anchor,
+                        thin,
+                        xml_escape(text)
+                    ));
+                }
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    fn svg_style(&self, layer: Layer) -> String {
+        let d = self.diameter;
+        match layer {
+            Layer::Outline => format!("stroke=\"black\" stroke-width=\"{:.4}\"", 0.02 * d),
+            Layer::Center => format!(
+                "stroke=\"blue\" stroke-width=\"{:.4}\" stroke-dasharray=\"{:.4} {:.4} {:.4} {:.4}\"",
+                0.01 * d,
+                0.3 * d,
+                0.06 * d,
+                0.06 * d,
+                0.06 * d
+            ),
+            Layer::Dimension => format!("stroke=\"gray\" stroke-width=\"{:.4}\"", 0.01 * d),
+        }
+    }
+
+    /// AutoCAD R12 ASCII DXF in mm, with the outline, centre line and
+    /// dimensions on separate layers. The nose is a true arc.
+    pub fn to_dxf(&self) -> String {
+        let mut out = String::new();
+        let mut pair = |code: i32, value: &str| out.push_str(&format!("{}\n{}\n", code, value));
+
+        pair(0, "SECTION");
+        pair(2, "HEADER");
+        pair(9, "$ACADVER");
+        pair(1, "AC1009");
+        pair(9, "$INSUNITS");
+        pair(70, "4");
+        pair(0, "ENDSEC");
+
+        let dash = 0.3 * self.diameter;
+        let gap = 0.06 * self.diameter;
+        pair(0, "SECTION");
+        pair(2, "TABLES");
+        pair(0, "TABLE");
+        pair(2, "LTYPE");
+        pair(70, "2");
+        pair(0, "LTYPE");
+        pair(2, "CONTINUOUS");
+        pair(70, "0");
+        pair(3, "Solid line");
+        pair(72, "65");
+        pair(73, "0");
+        pair(40, "0.0");
+        pair(0, "LTYPE");
+        pair(2, "CENTER");
+        pair(70, "0");
+        pair(3, "Center ____ _ ____");
+        pair(72, "65");
+        pair(73, "4");
+        pair(40, &format!("{:.4}", dash + 3.0 * gap));
+        pair(49, &format!("{:.4}", dash));
+        pair(49, &format!("{:.4}", -gap));
+        pair(49, &format!("{:.4}", gap));
+        pair(49, &format!("{:.4}", -gap));
+        pair(0, "ENDTAB");
+        pair(0, "TABLE");
+        pair(2, "LAYER");
+        pair(70, "3");
+        for (layer, color, linetype) in [
+            (Layer::Outline, "7", "CONTINUOUS"),
+            (Layer::Center, "5", "CENTER"),
+            (Layer::Dimension, "8", "CONTINUOUS"),
+        ] {
+            pair(0, "LAYER");
+            pair(2, layer.name());
+            pair(70, "0");
+            pair(62, color);
+            pair(6, linetype);
+        }
+        pair(0, "ENDTAB");
+        pair(0, "ENDSEC");
+
+        pair(0, "SECTION");
+        pair(2, "ENTITIES");
+        for entity in &self.entities {
+            match entity {
+                Entity::Line { layer, from, to } => {
+                    pair(0, "LINE");
+                    pair(8, layer.name());
+                    pair(10, &format!("{:.4}", from.0));
+                    pair(20, &format!("{:.4}", from.1));
+                    pair(30, "0.0");
+                    pair(11, &format!("{:.4}", to.0));
+                    pair(21, &format!("{:.4}", to.1));
+                    pair(31, "0.0");
+                }
+                Entity::Arc { layer, center, radius, start, end } => {
+                    pair(0, "ARC");
+                    pair(8, layer.name());
+                    pair(10, &format!("{:.4}", center.0));
+                    pair(20, &format!("{:.4}", center.1));
+                    pair(30, "0.0");
+                    pair(40, &format!("{:.4}", radius));
+                    pair(50, &format!("{:.4}", start));
+                    pair(51, &format!("{:.4}", end));
+                }
+                Entity::Text { position, height, align, text } => {
+                    pair(0, "TEXT");
+                    pair(8, Layer::Dimension.name());
+                    pair(10, &format!("{:.4}", position.0));
+                    pair(20, &format!("{:.4}", position.1));
+                    pair(30, "0.0");
+                    pair(40, &format!("{:.4}", height));
+                    pair(1, text);
+                    let justify = match align {
+                        Align::Left => 0,
+                        Align::Center => 1,
+                        Align::Right => 2,
+                    };
+                    if justify != 0 {
+                        pair(72, &justify.to_string());
+                        pair(11, &format!("{:.4}", position.0));
+                        pair(21, &format!("{:.4}", position.1));
+                        pair(31, "0.0");
+                    }
+                }
+            }
+        }
+        pair(0, "ENDSEC");
+        pair(0, "EOF");
+        out
+    }
+}
+
+/// One piece of the body outline (x, r) in calibers: a straight line, or a
+/// circular arc about `center` swept from angle `from` to angle `to`
+/// (radians, counterclockwise from the +x axis), either way round.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Piece {
+    Line { from: (f64, f64), to: (f64, f64) },
+    Arc { center: (f64, f64), radius: f64, from: f64, to: f64 },
+}
+
+impl Piece {
+    fn point_at(center: (f64, f64), radius: f64, angle: f64) -> (f64, f64) {
+        (center.0 + radius * angle.cos(), center.1 + radius * angle.sin())
+    }
+
+    pub(crate) fn start(&self) -> (f64, f64) {
+        match *self {
+            Piece::Line { from, .. } => from,
+            Piece::Arc { center, radius, from, .. } => Piece::point_at(center, radius, from),
+        }
+    }
+
+    pub(crate) fn end(&self) -> (f64, f64) {
+        match *self {
+            Piece::Line { to, .. } => to,
+            Piece::Arc { center, radius, to, .. } => Piece::point_at(center, radius, to),
+        }
+    }
+
+    /// Points along the piece after its start: the end of a line, or
+    /// `steps` points at equal angles along an arc.
+    pub(crate) fn points(&self, steps: usize) -> Vec<(f64, f64)> {
+        match *self {
+            Piece::Line { to, .. } => vec![to],
+            Piece::Arc { center, radius, from, to } => (1..=steps)
+                .map(|i| Piece::point_at(center, radius, from + (to - from) * i as f64 / steps as f64))
+                .collect(),
+        }
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+impl ProjectileInput {
+    /// Checks that the contour can be drawn: positive lengths, a cylinder
+    /// between nose and boattail, a tip radius that spans the meplat, a tip
+    /// cavity inside it, a base cavity and edge radius that fit the base, a
+    /// boattail shape that fits, the bands and placed grooves on that
+    /// cylinder, and fins that fit the body. A sphere needs only its
+    /// diameter.
+    pub(crate) fn check_profile(&self, band: Option<&BandPosition>) -> Result<(), String> {
+        if self.is_sphere() {
+            return if self.ref_diameter > 0.0 {
+                Ok(())
+            } else {
+                Err("Reference diameter must be positive".to_string())
+            };
+        }
+        if self.ref_diameter <= 0.0 || self.total_length <= 0.0 || self.nose_length <= 0.0 {
+            return Err("Reference diameter, total length and nose length must be positive".to_string());
+        }
+        let boattail_start = self.total_length - self.boattail_length;
+        if boattail_start < self.nose_length {
+            return Err("Nose and boattail are longer than the projectile".to_string());
+        }
+        if self.compound_nose.is_some_and(|tip| !tip.fits(self.nose_length, self.meplat_diameter)) {
+            return Err("Compound nose tip does not fit the nose".to_string());
+        }
+        if let Some(NoseTip::Radiused { radius }) = self.nose_tip {
+            if radius < 0.5 * self.meplat_diameter {
+                return Err("Tip radius is smaller than half the meplat diameter".to_string());
+            }
+        }
+        if self
+            .tip_cavity
+            .is_some_and(|cavity| !cavity.fits(self.meplat_diameter) || cavity.depth >= self.total_length)
+        {
+            return Err("Tip cavity does not fit the meplat".to_string());
+        }
+        if !self.boattail_shape_fits() {
+            return Err("Boattail radius or rebate does not fit the boattail".to_string());
+        }
+        if !self.bands.is_empty() {
+            if !self.bands_fit() {
+                return Err("Bands must lie on the cylindrical part of the body, clear of each other".to_string());
+            }
+        } else if let Some(band) = band {
+            if self.band_diameter <= 1.0 {
+                return Err("Band position given but the band diameter is not above one caliber".to_string());
+            }
+            if band.width <= 0.0 || band.location < self.nose_length || band.location + band.width > boattail_start {
+                return Err("Band must lie on the cylindrical part of the body".to_string());
+            }
+        }
+        if self.fins.is_some_and(|fins| !fins.is_valid() || fins.root_chord > self.total_length) {
+            return Err("Fins must have positive dimensions, be thinner than their chord and have a root chord no \
+                        longer than the projectile"
+                .to_string());
+        }
+        let grooves = self.drawn_grooves();
+        if !grooves.is_empty() {
+            let rings = self.cylinder_rings(band);
+            if grooves.iter().any(|groove| groove.0 < self.nose_length || groove.0 + groove.1 > boattail_start)
+                || grooves.iter().any(|groove| groove.1 <= 0.0 || groove.2 <= 0.0)
+                || rings.windows(2).any(|pair| pair[0].0 + pair[0].1 > pair[1].0)
+            {
+                return Err("Grooves must lie on the cylindrical part of the body, clear of each other and the bands"
+                    .to_string());
+            }
+        }
+        if let Some(shape) = self.base_shape {
+            let edge = shape.edge_radius > 0.0
+                && self.side_contour(band).last().and_then(|&run| self.base_edge(run)).is_none();
+            if !shape.fits(self.base_diameter) || edge {
+                return Err("Base cavity or edge radius does not fit the base".to_string());
+            }
+            let tip = self.tip_cavity.map_or(0.0, |cavity| cavity.depth);
+            if shape.drawn_cavity(self.base_diameter).is_some_and(|(_, depth)| tip + depth >= self.total_length) {
+                return Err("Tip and base cavities meet".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Bands standing above the body, in order from the nose: those of
+    /// `bands`, or McCoy's single band of `band_diameter` where `band`
+    /// places it.
+    pub(crate) fn drawn_bands(&self, band: Option<&BandPosition>) -> Vec<Band> {
+        let mut bands = if self.bands.is_empty() {
+            band.map(|band| Band {
+                kind: BandKind::Rotating,
+                location: band.location,
+                width: band.width,
+                diameter: self.band_diameter,
+            })
+            .into_iter()
+            .collect()
+        } else {
+            self.bands.clone()
+        };
+        bands.retain(|band| band.diameter > 1.0);
+        bands.sort_by(|a, b| a.location.total_cmp(&b.location));
+        bands
+    }
+
+    /// Grooves placed on the body, in order from the nose: start, width and
+    /// diameter at the bottom of the groove, in calibers.
+    pub(crate) fn drawn_grooves(&self) -> Vec<(f64, f64, f64)> {
+        self.surface.map_or_else(Vec::new, |surface| {
+            surface
+                .groove_starts()
+                .into_iter()
+                .map(|start| (start, surface.groove_width, 1.0 - 2.0 * surface.groove_depth))
+                .collect()
+        })
+    }
+
+    /// Bands and placed grooves on the cylinder, in order from the nose:
+    /// start, width and diameter in calibers.
+    pub(crate) fn cylinder_rings(&self, band: Option<&BandPosition>) -> Vec<(f64, f64, f64)> {
+        let mut rings: Vec<(f64, f64, f64)> = self
+            .drawn_bands(band)
+            .iter()
+            .map(|band| (band.location, band.width, band.diameter))
+            .chain(self.drawn_grooves())
+            .collect();
+        rings.sort_by(|a, b| a.0.total_cmp(&b.0));
+        rings
+    }
+
+    /// Contour (x, r) in calibers of the cylinder from the nose shoulder to
+    /// the start of the boattail, stepping up over each band and down into
+    /// each groove. Between rings that touch, the step goes straight from
+    /// one to the next.
+    pub(crate) fn cylinder_points(&self, band: Option<&BandPosition>) -> Vec<(f64, f64)> {
+        let mut points: Vec<(f64, f64)> = vec![(self.nose_length, 0.5)];
+        let mut push = |point: (f64, f64)| {
+            let n = points.len();
+            if points[n - 1] == point {
+                return;
+            }
+            if n >= 2 && points[n - 2].0 == point.0 && points[n - 1].0 == point.0 {
+                points.pop();
+            }
+            points.push(point);
+        };
+        for (start, width, diameter) in self.cylinder_rings(band) {
+            let (end, r) = (start + width, 0.5 * diameter);
+            for point in [(start, 0.5), (start, r), (end, r), (end, 0.5)] {
+                push(point);
+            }
+        }
+        push((self.total_length - self.boattail_length, 0.5));
+        points
+    }
+
+    /// Outline of the side of the body in calibers, piece by piece from the
+    /// tip on the axis to the base corner: the tip with any cavity in it,
+    /// the nose segments, the cylinder with its bands and grooves, and the
+    /// boattail.
+    fn side_contour(&self, band: Option<&BandPosition>) -> Vec<Piece> {
+        let mut pieces = Vec::new();
+        let tip = 0.5 * self.meplat_diameter;
+        let (mouth, depth) = self.tip_cavity.map_or((0.0, 0.0), |cavity| (0.5 * cavity.diameter, cavity.depth));
+        match self.tip_cap().filter(|cap| tip > 0.0 && cap.height > 0.0) {
+            Some(cap) => {
+                // The cap stands ahead of the meplat, centred on the axis;
+                // a cavity opens through its middle.
+                let half_angle = cap.half_angle.to_radians();
+                let center = (cap.radius * half_angle.cos(), 0.0);
+                let open = (mouth / cap.radius).asin();
+                if mouth > 0.0 {
+                    let rim = (center.0 - cap.radius * open.cos(), mouth);
+                    pieces.push(Piece::Line { from: (depth, 0.0), to: (depth, mouth) });
+                    pieces.push(Piece::Line { from: (depth, mouth), to: rim });
+                }
+                pieces.push(Piece::Arc {
+                    center,
+                    radius: cap.radius,
+                    from: std::f64::consts::PI - open,
+                    to: std::f64::consts::PI - half_angle,
+                });
+            }
+            None if mouth > 0.0 => {
+                pieces.push(Piece::Line { from: (depth, 0.0), to: (depth, mouth) });
+                pieces.push(Piece::Line { from: (depth, mouth), to: (0.0, mouth) });
+                pieces.push(Piece::Line { from: (0.0, mouth), to: (0.0, tip) });
+            }
+            None => pieces.push(Piece::Line { from: (0.0, 0.0), to: (0.0, tip) }),
+        }
+        for segment in self.nose_segments() {
+            pieces.push(match segment.arc {
+                Some((xc, yc, radius)) => Piece::Arc {
+                    center: (xc, yc),
+                    radius,
+                    from: (segment.start.1 - yc).atan2(segment.start.0 - xc),
+                    to: (segment.end.1 - yc).atan2(segment.end.0 - xc),
+                },
+                None => Piece::Line { from: segment.start, to: segment.end },
+            });
+        }
+        for pair in self.cylinder_points(band).windows(2) {
+            pieces.push(Piece::Line { from: pair[0], to: pair[1] });
+        }
+        if self.boattail_length > 0.0 {
+            let start = self.total_length - self.boattail_length;
+            let mut from = (start, self.boattail_shoulder());
+            pieces.push(Piece::Line { from: (start, 0.5), to: from });
+            if let Some((center, radius, angle)) = self.boattail_arc() {
+                let up = std::f64::consts::FRAC_PI_2;
+                pieces.push(Piece::Arc { center, radius, from: up, to: up - angle });
+                from = (center.0 + radius * angle.sin(), center.1 + radius * angle.cos());
+            }
+            pieces.push(Piece::Line { from, to: (self.total_length, 0.5 * self.base_diameter) });
+        }
+        pieces.retain(|piece| piece.start() != piece.end());
+        pieces
+    }
+
+    /// Outline of the body above the axis in calibers, piece by piece from
+    /// the tip on the axis to the base on the axis: the side, then any base
+    /// edge radius, the base face and any base cavity.
+    pub(crate) fn contour(&self, band: Option<&BandPosition>) -> Vec<Piece> {
+        let mut pieces = self.side_contour(band);
+
+        // The base edge radius is tangent to the side running into the base
+        // and to the base face; the face steps into any base cavity.
+        let shape = self.base_shape.unwrap_or_default();
+        let mut face = pieces[pieces.len() - 1].end();
+        if shape.edge_radius > 0.0 {
+            if let Some((run, arc)) = self.base_edge(pieces[pieces.len() - 1]) {
+                face = arc.end();
+                pieces.pop();
+                pieces.extend([run, arc]);
+            }
+        }
+        let length = self.total_length;
+        let mut bottom = (length, 0.0);
+        if let Some((diameter, depth)) = shape.drawn_cavity(self.base_diameter) {
+            let mouth = 0.5 * diameter;
+            pieces.push(Piece::Line { from: face, to: (length, mouth) });
+            pieces.push(Piece::Line { from: (length, mouth), to: (length - depth, mouth) });
+            face = (length - depth, mouth);
+            bottom = (length - depth, 0.0);
+        }
+        pieces.push(Piece::Line { from: face, to: bottom });
+        pieces.retain(|piece| piece.start() != piece.end());
+        pieces
+    }
+
+    /// Base edge radius in place of the straight `run` into the base
+    /// corner: the run cut back to the tangent point and the arc down to
+    /// the base face. `None` when the run is not a line long enough for
+    /// the radius.
+    fn base_edge(&self, run: Piece) -> Option<(Piece, Piece)> {
+        let radius = self.base_shape.map_or(0.0, |shape| shape.edge_radius);
+        let Piece::Line { from, to } = run else {
+            return None;
+        };
+        if to.0 <= from.0 {
+            return None;
+        }
+        // Half-angle at which the side closes in towards the base.
+        let angle = ((from.1 - to.1) / (to.0 - from.0)).atan();
+        let (sin, cos) = angle.sin_cos();
+        let center = (to.0 - radius, to.1 + radius * ((1.0 - sin) * angle.tan() - cos));
+        let tangent = (center.0 + radius * sin, center.1 + radius * cos);
+        (tangent.0 >= from.0 && center.1 >= 0.0).then_some((
+            Piece::Line { from, to: tangent },
+            Piece::Arc { center, radius, from: std::f64::consts::FRAC_PI_2 - angle, to: 0.0 },
+        ))
+    }
+
+    /// Dimensioned profile drawing of the contour MCDRAG analyzes: meplat
+    /// or rounded tip with any cavity, ogive or cone nose, cylinder with
+    /// any placed grooves, boattail with any rebate step and transition
+    /// arc, base with any edge radius and cavity, and fins, with the bands
+    /// of `bands`, or the rotating band where `band` places it. Dimensions
+    /// are given in mm and calibers.
+    pub fn profile_drawing(&self, band: Option<&BandPosition>) -> Result<Drawing, String> {
+        self.check_profile(band)?;
+        if self.is_sphere() {
+            return Ok(self.sphere_drawing());
+        }
+        let boattail_start = self.total_length - self.boattail_length;
+
+        let d = self.ref_diameter;
+        let mm = |calibers: f64| calibers * d;
+        let label = |calibers: f64| format!("{:.2} MM / {:.3} CAL", mm(calibers), calibers);
+
+        let mut drawing = Drawing { diameter: d, entities: Vec::new() };
+        let length = mm(self.total_length);
+        let nose = mm(self.nose_length);
+        let tip = mm(0.5 * self.meplat_diameter);
+        let radius = mm(0.5);
+        let base = if self.boattail_length > 0.0 { mm(0.5 * self.base_diameter) } else { radius };
+        let tail = mm(boattail_start);
+
+        drawing.line(Layer::Center, (-0.3 * d, 0.0), (length + 0.3 * d, 0.0));
+
+        for piece in self.contour(band) {
+            match piece {
+                // The tip and base faces cross the axis as one line.
+                Piece::Line { from, to } if from.0 == to.0 && (from.1 == 0.0 || to.1 == 0.0) => {
+                    let r = mm(from.1.max(to.1));
+                    drawing.line(Layer::Outline, (mm(from.0), -r), (mm(from.0), r));
+                }
+                Piece::Line { from, to } => drawing.outline((mm(from.0), mm(from.1)), (mm(to.0), mm(to.1))),
+                Piece::Arc { center, radius, from, to } => {
+                    let (start, end) = (from.min(to).to_degrees(), from.max(to).to_degrees());
+                    for (center, start, end) in [((center.0, center.1), start, end), ((center.0, -center.1), -end, -start)] {
+                        drawing.entities.push(Entity::Arc {
+                            layer: Layer::Outline,
+                            center: (mm(center.0), mm(center.1)),
+                            radius: mm(radius),
+                            start,
+                            end,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Lengths below the body, band position above it.
+        let near = -radius - 0.5 * d;
+        let far = -radius - d;
+        drawing.horizontal_dimension((0.0, -tip), (nose, -radius), near, format!("NOSE {}", label(self.nose_length)));
+        if self.boattail_length > 0.0 {
+            drawing.horizontal_dimension(
+                (tail, -radius),
+                (length, -base),
+                near,
+                format!("BOATTAIL {}", label(self.boattail_length)),
+            );
+        }
+        drawing.horizontal_dimension((0.0, -tip), (length, -base), far, format!("LENGTH {}", label(self.total_length)));
+
+        let bands = self.drawn_bands(band);
+        let highest = bands.iter().fold(radius, |highest, band| highest.max(mm(0.5 * band.diameter)));
+        for (i, band) in bands.iter().enumerate() {
+            let name = match &band.kind {
+                BandKind::Rotating => "BAND",
+                kind => kind.to_str(),
+            };
+            drawing.horizontal_dimension(
+                (0.0, tip),
+                (mm(band.location), mm(0.5 * band.diameter)),
+                highest + (0.7 + 0.4 * i as f64) * d,
+                format!(
+                    "{} AT {}, WIDTH {}, DIA {}",
+                    name,
+                    label(band.location),
+                    label(band.width),
+                    label(band.diameter)
+                ),
+            );
+        }
+        let grooves = self.drawn_grooves();
+        for (i, &(start, width, diameter)) in grooves.iter().enumerate() {
+            drawing.horizontal_dimension(
+                (0.0, tip),
+                (mm(start), mm(0.5 * diameter)),
+                highest + (0.7 + 0.4 * (bands.len() + i) as f64) * d,
+                format!("GROOVE AT {}, WIDTH {}, DEPTH {}", label(start), label(width), label(0.5 - 0.5 * diameter)),
+            );
+        }
+        if let Some(cavity) = self.tip_cavity {
+            drawing.horizontal_dimension(
+                (0.0, tip),
+                (mm(cavity.depth), mm(0.5 * cavity.diameter)),
+                highest + (0.7 + 0.4 * (bands.len() + grooves.len()) as f64) * d,
+                format!("TIP CAVITY DEPTH {}, DIA {}", label(cavity.depth), label(cavity.diameter)),
+            );
+        }
+        let shape = self.base_shape.unwrap_or_default();
+        if let Some((diameter, depth)) = shape.drawn_cavity(self.base_diameter) {
+            let name = if shape.cup_depth > 0.0 {
+                "BASE CUP"
+            } else if shape.hollow_diameter > 0.0 {
+                "HOLLOW BASE"
+            } else {
+                "TRACER CAVITY (DRAWN)"
+            };
+            drawing.horizontal_dimension(
+                (mm(self.total_length - depth), mm(0.5 * diameter)),
+                (length, base),
+                highest + (0.7 + 0.4 * (bands.len() + grooves.len() + self.tip_cavity.iter().count()) as f64) * d,
+                format!("{} DEPTH {}, DIA {}", name, label(depth), label(diameter)),
+            );
+        }
+        if self.bands.is_empty() && band.is_none() && self.band_diameter > 1.0 {
+            drawing.text(
+                (0.5 * (nose + tail), radius + 0.15 * d),
+                Align::Center,
+                format!("BAND DIA {} (POSITION NOT GIVEN)", label(self.band_diameter)),
+            );
+        }
+
+        // Diameters beside the ends of the body.
+        if tip > 0.0 {
+            drawing.diameter_dimension(0.0, tip, -0.5 * d, format!("MEPLAT {}", label(self.meplat_diameter)));
+        }
+        let mut reference_at = length + 0.5 * d;
+        if self.boattail_length > 0.0 {
+            let text = format!("BASE {}", label(self.base_diameter));
+            reference_at += 0.6 * 0.12 * d * text.len() as f64 + 0.4 * d;
+            drawing.diameter_dimension(length, base, length + 0.5 * d, text);
+        }
+        drawing.diameter_dimension(
+            if self.boattail_length > 0.0 { tail } else { length },
+            radius,
+            reference_at,
+            format!("DIA {:.2} MM", d),
+        );
+
+        let mut nose_note = match self.ogive_arc() {
+            Some((_, _, r)) => format!("OGIVE R {}  RT/R {:.3}", label(r), self.rt_r),
+            None => "CONICAL NOSE".to_string(),
+        };
+        if let Some(tip) = self.compound_nose {
+            nose_note.push_str(&format!(
+                "  TIP {} TO DIA {}  RT/R {:.3}",
+                label(tip.tip_length),
+                label(tip.tip_diameter),
+                tip.tip_rt_r
+            ));
+        }
+        if let Some(cap) = self.tip_cap().filter(|cap| tip > 0.0 && cap.height > 0.0) {
+            nose_note.push_str(&format!(
+                "  {} R {} HEIGHT {}",
+                match self.nose_tip {
+                    Some(NoseTip::Polymer) => "POLYMER TIP",
+                    _ => "TIP",
+                },
+                label(cap.radius),
+                label(cap.height)
+            ));
+        }
+        drawing.text((0.1 * d, radius + 0.35 * d), Align::Left, nose_note);
+
+        let cg = self.effective_cg();
+        if cg > 0.0 && cg < self.total_length {
+            let x = mm(cg);
+            let size = 0.1 * d;
+            drawing.line(Layer::Dimension, (x - size, 0.0), (x + size, 0.0));
+            drawing.line(Layer::Dimension, (x, -size), (x, size));
+            drawing.text((x + size, 0.05 * d), Align::Left, format!("CG {:.3} CAL", cg));
+        }
+
+        // Notes below the dimensions, then the identification.
+        let mut notes = Vec::new();
+        if let Some(shape) = self.boattail_shape {
+            notes.push(format!(
+                "BOATTAIL R {}  REBATE {}  EFFECTIVE ANGLE {:.2} DEG",
+                label(shape.transition_radius),
+                label(shape.rebate_depth),
+                self.effective_boattail_angle()
+            ));
+        }
+        if shape.edge_radius > 0.0 {
+            notes.push(format!("BASE EDGE R {}", label(shape.edge_radius)));
+        }
+        if let (Some(fins), Some(planform)) = (self.fins, self.fin_planform()) {
+            // The fins in the plane of the drawing, above and below the
+            // body; the root is hidden by it.
+            let [root_leading, root_trailing, tip_trailing, tip_leading] = planform;
+            for (from, to) in [(root_trailing, tip_trailing), (tip_trailing, tip_leading), (tip_leading, root_leading)] {
+                drawing.outline((mm(from.0), mm(from.1)), (mm(to.0), mm(to.1)));
+            }
+            notes.push(format!(
+                "{} FINS  ROOT {}  TIP {}  SPAN {}  THICKNESS {}  {} LEADING EDGE",
+                fins.count,
+                label(fins.root_chord),
+                label(fins.tip_chord),
+                label(fins.span),
+                label(fins.thickness),
+                fins.leading_edge.to_str()
+            ));
+        }
+        for (i, note) in notes.iter().enumerate() {
+            drawing.text((0.0, far - (0.35 + 0.25 * i as f64) * d), Align::Left, note.clone());
+        }
+
+        drawing.text(
+            (0.0, far - (0.35 + 0.25 * notes.len().max(1) as f64) * d),
+            Align::Left,
+            format!("{}  BOUNDARY LAYER {}", self.identification, self.boundary_layer.to_str()),
+        );
+        Ok(drawing)
+    }
+
+    /// Drawing of a sphere: the circle as two true arcs about the axis, with
+    /// its diameter.
+    fn sphere_drawing(&self) -> Drawing {
+        let d = self.ref_diameter;
+        let radius = 0.5 * d;
+        let mut drawing = Drawing { diameter: d, entities: Vec::new() };
+
+        drawing.line(Layer::Center, (-0.3 * d, 0.0), (d + 0.3 * d, 0.0));
+        for (start, end) in [(0.0, 180.0), (180.0, 360.0)] {
+            drawing.entities.push(Entity::Arc {
+                layer: Layer::Outline,
+                center: (radius, 0.0),
+                radius,
+                start,
+                end,
+            });
+        }
+        drawing.diameter_dimension(radius, radius, d + 0.5 * d, format!("DIA {:.2} MM", d));
+        drawing.text(
+            (0.0, -radius - 0.6 * d),
+            Align::Left,
+            format!("{}  SPHERE  BOUNDARY LAYER {}", self.identification, self.boundary_layer.to_str()),
+        );
+        drawing
+    }
+}
+
+/// Exports the profile drawing in the requested format.
+pub fn export_profile(input: &ProjectileInput, request: &DrawingRequest) -> Result<String, String> {
+    input
+        .profile_drawing(request.band.as_ref())
+        .map(|drawing| drawing.format(request.format))
+}
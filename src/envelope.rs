@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{DragCoefficients, ProjectileInput};
+
+/// Range of each parameter covered by the projectiles McCoy fitted the
+/// MCDRAG correlations to: name, lowest, highest and the margin inside
+/// either limit that counts as near the edge. Lengths and diameters in
+/// calibers, the boattail angle in degrees.
+const ENVELOPE: [(&str, f64, f64, f64); 6] = [
+    ("TOTAL LENGTH", 3.0, 6.0, 0.3),
+    ("NOSE LENGTH", 1.0, 4.0, 0.2),
+    ("BOATTAIL LENGTH", 0.0, 1.5, 0.15),
+    ("BOATTAIL ANGLE", 0.0, 9.0, 1.0),
+    ("MEPLAT DIAMETER", 0.0, 0.5, 0.05),
+    ("MACH", 0.5, 4.0, 0.1),
+];
+
+/// McCoy's stated CD0 accuracy inside the envelope: subsonic, transonic
+/// (0.9 <= M <= 1.2) and supersonic, as fractions of CD0.
+const SUBSONIC_ACCURACY: f64 = 0.03;
+const TRANSONIC_ACCURACY: f64 = 0.10;
+const SUPERSONIC_ACCURACY: f64 = 0.05;
+
+/// Widening of the accuracy band near the edge of the envelope and outside it.
+const NEAR_EDGE_FACTOR: f64 = 1.5;
+const EXTRAPOLATING_FACTOR: f64 = 2.0;
+
+/// Where a case lies relative to the correlation database.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum Validity {
+    Inside,
+    NearEdge,
+    Extrapolating,
+}
+
+impl Validity {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Validity::Inside => "INSIDE",
+            Validity::NearEdge => "NEAR EDGE",
+            Validity::Extrapolating => "EXTRAPOLATING",
+        }
+    }
+}
+
+/// Envelope status of one Mach row. `uncertainty` is the fractional
+/// accuracy band on CD0 and `limits` names each parameter near or beyond
+/// the edge of the database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopeRow {
+    pub mach: f64,
+    pub cd0: f64,
+    pub validity: Validity,
+    pub uncertainty: f64,
+    pub cd0_low: f64,
+    pub cd0_high: f64,
+    pub limits: Vec<String>,
+}
+
+/// Status of one parameter against its envelope range, with a note when it
+/// is not comfortably inside. A lower limit of 0 (no boattail, a sharp
+/// point) is a common shape rather than an edge of the data.
+fn check(name: &str, value: f64, low: f64, high: f64, margin: f64) -> (Validity, Option<String>) {
+    let near_low = low > 0.0 && value < low + margin;
+    if value < low || value > high {
+        let side = if value < low { format!("BELOW {}", low) } else { format!("ABOVE {}", high) };
+        (Validity::Extrapolating, Some(format!("{} {:.3} {}", name, value, side)))
+    } else if near_low || value > high - margin {
+        (Validity::NearEdge, Some(format!("{} {:.3} NEAR {}", name, value, if near_low { low } else { high })))
+    } else {
+        (Validity::Inside, None)
+    }
+}
+
+impl ProjectileInput {
+    /// Validity of each row of a drag table against McCoy's correlation
+    /// database, with his accuracy band on CD0 widened by half near the edge
+    /// of the database and doubled when extrapolating. A sphere has no
+    /// slender-body geometry to check, only the Mach number.
+    pub fn validity_envelope(&self, drag: &[DragCoefficients]) -> Vec<EnvelopeRow> {
+        let geometry = [
+            self.total_length,
+            self.nose_length,
+            self.boattail_length,
+            self.effective_boattail_angle().abs(),
+            self.meplat_diameter,
+        ];
+
+        drag.iter()
+            .map(|row| {
+                let (validity, limits) = ENVELOPE
+                    .iter()
+                    .zip(geometry.iter().chain(std::iter::once(&row.mach)))
+                    .skip(if self.is_sphere() { geometry.len() } else { 0 })
+                    .map(|(&(name, low, high, margin), &value)| check(name, value, low, high, margin))
+                    .fold((Validity::Inside, Vec::new()), |(worst, mut limits), (validity, note)| {
+                        limits.extend(note);
+                        (if validity > worst { validity } else { worst }, limits)
+                    });
+
+                let base = if row.mach < 0.9 {
+                    SUBSONIC_ACCURACY
+                } else if row.mach <= 1.2 {
+                    TRANSONIC_ACCURACY
+                } else {
+                    SUPERSONIC_ACCURACY
+                };
+                let uncertainty = base * match validity {
+                    Validity::Inside => 1.0,
+                    Validity::NearEdge => NEAR_EDGE_FACTOR,
+                    Validity::Extrapolating => EXTRAPOLATING_FACTOR,
+                };
+
+                EnvelopeRow {
+                    mach: row.mach,
+                    cd0: row.cd0,
+                    validity,
+                    uncertainty,
+                    cd0_low: row.cd0 * (1.0 - uncertainty),
+                    cd0_high: row.cd0 * (1.0 + uncertainty),
+                    limits,
+                }
+            })
+            .collect()
+    }
+}
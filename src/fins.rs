@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{BoundaryLayer, ProjectileInput};
+
+/// Pitot pressure coefficient behind a normal shock at Mach 1. Below a
+/// sonic normal Mach number the edge pressure is mostly recovered, and what
+/// remains rises with McCoy's band exponent.
+const SONIC_PITOT: f64 = 1.2757;
+const SUBSONIC_EXPONENT: f64 = 12.5;
+
+/// Smallest Prandtl-Glauert factor used for thin-section wave drag, that of
+/// Mach 1.2; linear theory diverges towards Mach 1.
+const MIN_BETA: f64 = 0.6633;
+
+/// Thickness-to-chord ratio above which the fins are no longer thin
+/// sections.
+const MAX_THICKNESS_RATIO: f64 = 0.15;
+
+/// Section shape of the fin leading edge.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LeadingEdge {
+    /// Flat plate cut square, with a square trailing edge.
+    Square,
+    /// Flat plate with a semicircular leading edge and a square trailing
+    /// edge.
+    Rounded,
+    /// Hexagonal section, wedged at both edges with the half-angle in
+    /// degrees measured normal to the edge.
+    Wedge { half_angle: f64 },
+}
+
+impl LeadingEdge {
+    pub fn to_str(&self) -> &str {
+        match self {
+            LeadingEdge::Square => "SQUARE",
+            LeadingEdge::Rounded => "ROUNDED",
+            LeadingEdge::Wedge { .. } => "WEDGE",
+        }
+    }
+
+    /// Share of the pitot pressure a blunt edge carries over its frontal
+    /// area: all of it on a square face, two thirds around a half-cylinder
+    /// (Newtonian). A wedge has no blunt edge.
+    fn bluntness(&self) -> f64 {
+        match self {
+            LeadingEdge::Square => 1.0,
+            LeadingEdge::Rounded => 2.0 / 3.0,
+            LeadingEdge::Wedge { .. } => 0.0,
+        }
+    }
+}
+
+/// Fin set of a fin-stabilized projectile: number of fins, exposed span of
+/// each fin from the body, root and tip chords and thickness, all in
+/// calibers. The trailing edge is taken unswept, so the leading edge is
+/// swept by the difference of the chords.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FinSet {
+    pub count: u32,
+    pub span: f64,
+    pub root_chord: f64,
+    pub tip_chord: f64,
+    pub thickness: f64,
+    pub leading_edge: LeadingEdge,
+}
+
+impl FinSet {
+    /// Planform area of one fin in square calibers.
+    pub fn planform_area(&self) -> f64 {
+        0.5 * (self.root_chord + self.tip_chord) * self.span
+    }
+
+    pub fn mean_chord(&self) -> f64 {
+        0.5 * (self.root_chord + self.tip_chord)
+    }
+
+    /// Leading-edge sweep angle in radians.
+    pub fn sweep(&self) -> f64 {
+        ((self.root_chord - self.tip_chord) / self.span).atan()
+    }
+
+    /// Whether the fins have positive dimensions, are thinner than their
+    /// mean chord and any wedge angle is a real one.
+    pub fn is_valid(&self) -> bool {
+        let wedge = match self.leading_edge {
+            LeadingEdge::Wedge { half_angle } => half_angle > 0.0 && half_angle < 90.0,
+            _ => true,
+        };
+        self.count > 0
+            && self.span > 0.0
+            && self.root_chord > 0.0
+            && self.tip_chord >= 0.0
+            && self.thickness > 0.0
+            && self.thickness < self.mean_chord()
+            && wedge
+    }
+}
+
+impl ProjectileInput {
+    /// Fin skin friction, wave drag and base drag (CDFSF, CDFW, CDFB) at
+    /// `mach`, given McCoy's flat base pressure ratio `pb_pinf` at that
+    /// Mach number.
+    ///
+    /// Both faces of each fin have flat plate friction at the Reynolds
+    /// number of the mean chord, raised by the thickness. Wave drag is
+    /// worked out at the Mach number normal to the leading edge and swept
+    /// back to the flow: a blunt edge carries the pitot pressure over its
+    /// frontal area, a wedged section the linear theory drag of its wedges.
+    /// A square trailing edge is a base at `pb_pinf`.
+    pub fn fin_drag_at(&self, mach: f64, pb_pinf: f64) -> (f64, f64, f64) {
+        let Some(fins) = self.fins else {
+            return (0.0, 0.0, 0.0);
+        };
+        let count = fins.count as f64;
+        let m2 = mach * mach;
+
+        let (c7, c8) = self.friction_coefficients_at(mach, fins.mean_chord());
+        let cf = match self.boundary_layer {
+            BoundaryLayer::LaminarLaminar => c7,
+            _ => c8,
+        };
+        let form = 1.0 + 2.0 * fins.thickness / fins.mean_chord();
+        let cdfsf = 1.2732 * cf * form * 2.0 * fins.planform_area() * count;
+
+        let cos_sweep = fins.sweep().cos();
+        let normal = mach * cos_sweep;
+        let onset = normal.min(1.0).powf(SUBSONIC_EXPONENT);
+        let cdfw = match fins.leading_edge {
+            LeadingEdge::Wedge { half_angle } => {
+                let beta = (normal * normal - 1.0).max(MIN_BETA * MIN_BETA).sqrt();
+                let section = 4.0 * fins.thickness / fins.mean_chord() * half_angle.to_radians().tan() / beta;
+                1.2732 * section * cos_sweep * cos_sweep * fins.planform_area() * count * onset
+            }
+            edge => {
+                let pitot = if normal > 1.0 {
+                    let n2 = normal * normal;
+                    let pitot_ratio = (1.2 * n2).powf(3.5) * (2.4 / (2.8 * n2 - 0.4)).powf(2.5);
+                    (pitot_ratio - 1.0) / (0.7 * n2)
+                } else {
+                    SONIC_PITOT * onset
+                };
+                1.2732 * edge.bluntness() * pitot * cos_sweep * cos_sweep * fins.thickness * fins.span * count
+            }
+        };
+
+        let cdfb = if fins.leading_edge.bluntness() > 0.0 {
+            1.2732 * 1.4286 * (1.0 - pb_pinf) / m2 * fins.thickness * fins.span * count
+        } else {
+            0.0
+        };
+
+        (cdfsf, cdfw, cdfb)
+    }
+
+    /// Planform of one fin in its plane through the axis, (x, r) in
+    /// calibers and counterclockwise: root leading edge, root trailing edge
+    /// at the base, tip trailing edge, tip leading edge. The root lies on
+    /// the narrowest body section under it, so the fin meets the body along
+    /// its whole chord, and the span is measured from there. A sphere has
+    /// none.
+    pub fn fin_planform(&self) -> Option<[(f64, f64); 4]> {
+        let fins = self.fins.filter(|_| !self.is_sphere())?;
+        let length = self.total_length;
+        let leading = length - fins.root_chord;
+        let root = (0..=32)
+            .map(|i| self.radius_at(leading + fins.root_chord * i as f64 / 32.0))
+            .fold(f64::INFINITY, f64::min);
+        let tip = root + fins.span;
+        Some([(leading, root), (length, root), (length, tip), (length - fins.tip_chord, tip)])
+    }
+
+    /// Warns when the fins are too thick for the thin-section model.
+    pub fn fin_diagnostics(&self) -> Vec<String> {
+        let mut diagnostics = Vec::new();
+        if let Some(fins) = self.fins {
+            if fins.thickness > MAX_THICKNESS_RATIO * fins.mean_chord() {
+                diagnostics.push(format!(
+                    "FIN THICKNESS ABOVE {}% OF MEAN CHORD. CDFW AND CDFB MAY BE INCORRECT.",
+                    100.0 * MAX_THICKNESS_RATIO
+                ));
+            }
+        }
+        diagnostics
+    }
+}
@@ -0,0 +1,409 @@
+use serde::{Deserialize, Serialize};
+
+use crate::trajectory::{self, Impact, Setup, TrajectoryInput, MOA_PER_RADIAN};
+use crate::ProjectileInput;
+
+const MPS_PER_MPH: f64 = 0.44704;
+/// Quadrant elevation sweep for indirect-fire tables, in mils.
+const QE_STEP: f64 = 5.0;
+const QE_MAX: f64 = 1100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AngleUnit {
+    Moa,
+    Mil,
+}
+
+/// Wind speed the wind-drift column is normalized to: 10 mph or 1 m/s.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WindUnit {
+    TenMph,
+    MetersPerSecond,
+}
+
+impl WindUnit {
+    fn speed(&self) -> f64 {
+        match self {
+            WindUnit::TenMph => 10.0 * MPS_PER_MPH,
+            WindUnit::MetersPerSecond => 1.0,
+        }
+    }
+
+    fn label(&self) -> &str {
+        match self {
+            WindUnit::TenMph => "10 MPH",
+            WindUnit::MetersPerSecond => "1 M/S",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TableFormat {
+    Text,
+    Csv,
+    Html,
+}
+
+/// Sight adjustment per click, e.g. 0.25 MOA or 0.1 mil.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ClickValue {
+    pub value: f64,
+    pub unit: AngleUnit,
+}
+
+/// Direct-fire table request as sent from the web front end. Without a
+/// format the table is returned as JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FiringTableRequest {
+    pub trajectory: TrajectoryInput,
+    pub click: ClickValue,
+    pub wind_unit: WindUnit,
+    #[serde(default)]
+    pub format: Option<TableFormat>,
+}
+
+/// Indirect-fire table request, one table per charge muzzle velocity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuadrantElevationRequest {
+    pub trajectory: TrajectoryInput,
+    pub charges: Vec<f64>,
+    #[serde(default)]
+    pub format: Option<TableFormat>,
+}
+
+/// One line of a direct-fire table. Elevation is the sight correction from
+/// the zero, drift the total deflection under the table's own wind, and
+/// wind drift the deflection per wind unit of full-value crosswind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FiringTableRow {
+    pub range: f64,
+    pub drop: f64,
+    pub elevation_moa: f64,
+    pub elevation_mil: f64,
+    pub elevation_clicks: f64,
+    pub drift: f64,
+    pub velocity: f64,
+    pub energy: f64,
+    pub time: f64,
+    pub wind_drift: f64,
+    pub wind_drift_moa: f64,
+    pub wind_drift_mil: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FiringTable {
+    pub identification: String,
+    pub muzzle_velocity: f64,
+    pub zero_range: f64,
+    pub sight_height: f64,
+    pub click: ClickValue,
+    pub wind_unit: WindUnit,
+    pub rows: Vec<FiringTableRow>,
+}
+
+/// One line of an indirect-fire table: the quadrant elevation (mils) that
+/// lands the round at `range` in still air, with the flight data there.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuadrantElevationRow {
+    pub range: f64,
+    pub quadrant_elevation: f64,
+    pub time: f64,
+    pub drift: f64,
+    pub impact_velocity: f64,
+    pub angle_of_fall: f64,
+    pub max_ordinate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChargeTable {
+    pub muzzle_velocity: f64,
+    pub max_range: f64,
+    pub rows: Vec<QuadrantElevationRow>,
+}
+
+/// Builds a direct-fire table from the range card of `params`, with a
+/// second still-air run under a full-value crosswind of one `wind_unit`.
+pub fn firing_table(
+    input: &ProjectileInput,
+    params: &TrajectoryInput,
+    click: ClickValue,
+    wind_unit: WindUnit,
+) -> Result<FiringTable, String> {
+    if click.value <= 0.0 {
+        return Err("Click value must be positive".to_string());
+    }
+
+    let card = trajectory::range_card(input, params)?;
+    let crosswind = TrajectoryInput {
+        wind_speed: wind_unit.speed(),
+        wind_direction: 90.0,
+        ..params.clone()
+    };
+    let wind_card = trajectory::range_card(input, &crosswind)?;
+
+    let rows = card.rows.iter().zip(wind_card.rows.iter())
+        .map(|(row, wind)| {
+            let elevation = match click.unit {
+                AngleUnit::Moa => row.elevation_moa,
+                AngleUnit::Mil => row.elevation_mil,
+            };
+            let angle = (wind.wind_drift / row.range).atan();
+            FiringTableRow {
+                range: row.range,
+                drop: row.drop,
+                elevation_moa: row.elevation_moa,
+                elevation_mil: row.elevation_mil,
+                elevation_clicks: (elevation / click.value).round(),
+                drift: row.windage,
+                velocity: row.velocity,
+                energy: row.energy,
+                time: row.time,
+                wind_drift: wind.wind_drift.abs(),
+                wind_drift_moa: angle.abs() * MOA_PER_RADIAN,
+                wind_drift_mil: angle.abs() * 1000.0,
+            }
+        })
+        .collect();
+
+    Ok(FiringTable {
+        identification: input.identification.clone(),
+        muzzle_velocity: params.muzzle_velocity,
+        zero_range: params.zero_range,
+        sight_height: params.sight_height,
+        click,
+        wind_unit,
+        rows,
+    })
+}
+
+/// Builds indirect-fire tables, one per charge muzzle velocity, for ranges
+/// in steps of `params.range_step` up to `params.max_range` or the maximum
+/// range of the charge. Uses the low-angle branch (QE below the maximum
+/// range elevation) and the atmosphere of `params`, ignoring its wind.
+pub fn quadrant_elevation_tables(
+    input: &ProjectileInput,
+    params: &TrajectoryInput,
+    charges: &[f64],
+) -> Result<Vec<ChargeTable>, String> {
+    if params.range_step <= 0.0 {
+        return Err("Range step must be positive".to_string());
+    }
+
+    let setup = Setup::new(input, params, input.calculate_drag_coefficients())?;
+    let mut tables = Vec::new();
+
+    for &velocity in charges {
+        if velocity <= 0.0 {
+            return Err("Charge velocities must be positive".to_string());
+        }
+
+        // Sweep the low-angle branch until the range stops increasing,
+        // starting from the muzzle at zero elevation.
+        let mut sweep = vec![(0.0, Impact {
+            range: 0.0,
+            deflection: 0.0,
+            time: 0.0,
+            velocity,
+            angle_of_fall: 0.0,
+            max_ordinate: 0.0,
+        })];
+        let mut qe = QE_STEP;
+        while qe <= QE_MAX {
+            let impact = setup.impact(velocity, qe / 1000.0).ok_or_else(|| {
+                format!(
+                    "Charge of {} m/s at QE {} mil does not come down within the flight time limit",
+                    velocity, qe
+                )
+            })?;
+            if let Some((_, last)) = sweep.last() {
+                if impact.range <= last.range {
+                    break;
+                }
+            }
+            sweep.push((qe, impact));
+            qe += QE_STEP;
+        }
+
+        let max_range = sweep.last().map_or(0.0, |(_, impact)| impact.range);
+        let mut rows = Vec::new();
+        let mut range = params.range_step;
+        while range <= params.max_range.min(max_range) {
+            if let Some(pair) = sweep.windows(2).find(|w| w[1].1.range >= range) {
+                let ((qe0, a), (qe1, b)) = (&pair[0], &pair[1]);
+                let t = (range - a.range) / (b.range - a.range);
+                let lerp = |x: f64, y: f64| x + t * (y - x);
+                rows.push(QuadrantElevationRow {
+                    range,
+                    quadrant_elevation: lerp(*qe0, *qe1),
+                    time: lerp(a.time, b.time),
+                    drift: lerp(a.deflection, b.deflection),
+                    impact_velocity: lerp(a.velocity, b.velocity),
+                    angle_of_fall: lerp(a.angle_of_fall, b.angle_of_fall) * 1000.0,
+                    max_ordinate: lerp(a.max_ordinate, b.max_ordinate),
+                });
+            }
+            range += params.range_step;
+        }
+
+        tables.push(ChargeTable {
+            muzzle_velocity: velocity,
+            max_range,
+            rows,
+        });
+    }
+
+    Ok(tables)
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+impl FiringTable {
+    pub fn format(&self, format: TableFormat) -> String {
+        match format {
+            TableFormat::Text => self.to_text(),
+            TableFormat::Csv => self.to_csv(),
+            TableFormat::Html => self.to_html(),
+        }
+    }
+
+    fn click_label(&self) -> String {
+        match self.click.unit {
+            AngleUnit::Moa => format!("{} MOA", self.click.value),
+            AngleUnit::Mil => format!("{} MIL", self.click.value),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str("MCDRAG FIRING TABLE\n\n");
+        out.push_str(&format!("PROJECTILE IDENTIFICATION: {}\n\n", self.identification));
+        out.push_str(&format!(
+            "MUZZLE VELOCITY {:.1} M/S   ZERO RANGE {:.0} M   SIGHT HEIGHT {:.1} MM   CLICK {}\n\n",
+            self.muzzle_velocity, self.zero_range, self.sight_height, self.click_label()
+        ));
+        out.push_str(&format!(
+            " RANGE    DROP    ELEV    ELEV  CLICKS   DRIFT    VEL    ENERGY   TIME  WIND DRIFT PER {}\n",
+            self.wind_unit.label()
+        ));
+        out.push_str("  (M)     (CM)   (MOA)   (MIL)           (CM)  (M/S)     (J)     (S)    (CM)  (MOA)  (MIL)\n\n");
+        for row in &self.rows {
+            out.push_str(&format!(
+                "{:6.0} {:7.1} {:7.2} {:7.2} {:6.0} {:7.1} {:6.1} {:8.0} {:6.3} {:7.1} {:6.2} {:6.2}\n",
+                row.range, row.drop * 100.0, row.elevation_moa, row.elevation_mil,
+                row.elevation_clicks, row.drift * 100.0, row.velocity, row.energy, row.time,
+                row.wind_drift * 100.0, row.wind_drift_moa, row.wind_drift_mil
+            ));
+        }
+        out
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "range_m,drop_m,elevation_moa,elevation_mil,elevation_clicks,drift_m,velocity_mps,energy_j,time_s,wind_drift_m,wind_drift_moa,wind_drift_mil\n",
+        );
+        for row in &self.rows {
+            out.push_str(&format!(
+                "{},{:.4},{:.3},{:.3},{},{:.4},{:.2},{:.1},{:.4},{:.4},{:.3},{:.3}\n",
+                row.range, row.drop, row.elevation_moa, row.elevation_mil, row.elevation_clicks,
+                row.drift, row.velocity, row.energy, row.time, row.wind_drift,
+                row.wind_drift_moa, row.wind_drift_mil
+            ));
+        }
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<table class=\"firing-table\">\n");
+        out.push_str(&format!(
+            "<caption>{} &mdash; {:.1} m/s, zero {:.0} m, sight height {:.1} mm, click {}</caption>\n",
+            html_escape(&self.identification), self.muzzle_velocity, self.zero_range,
+            self.sight_height, self.click_label()
+        ));
+        out.push_str(&format!(
+            "<tr><th>Range (m)</th><th>Drop (cm)</th><th>Elev (MOA)</th><th>Elev (mil)</th><th>Clicks</th>\
+             <th>Drift (cm)</th><th>Velocity (m/s)</th><th>Energy (J)</th><th>Time (s)</th>\
+             <th>Wind drift per {} (cm)</th><th>(MOA)</th><th>(mil)</th></tr>\n",
+            self.wind_unit.label().to_lowercase()
+        ));
+        for row in &self.rows {
+            out.push_str(&format!(
+                "<tr><td>{:.0}</td><td>{:.1}</td><td>{:.2}</td><td>{:.2}</td><td>{:.0}</td>\
+                 <td>{:.1}</td><td>{:.1}</td><td>{:.0}</td><td>{:.3}</td>\
+                 <td>{:.1}</td><td>{:.2}</td><td>{:.2}</td></tr>\n",
+                row.range, row.drop * 100.0, row.elevation_moa, row.elevation_mil,
+                row.elevation_clicks, row.drift * 100.0, row.velocity, row.energy, row.time,
+                row.wind_drift * 100.0, row.wind_drift_moa, row.wind_drift_mil
+            ));
+        }
+        out.push_str("</table>\n");
+        out
+    }
+}
+
+impl ChargeTable {
+    pub fn format(&self, format: TableFormat) -> String {
+        match format {
+            TableFormat::Text => self.to_text(),
+            TableFormat::Csv => self.to_csv(),
+            TableFormat::Html => self.to_html(),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!(
+            "CHARGE MUZZLE VELOCITY {:.1} M/S   MAXIMUM RANGE {:.0} M\n\n",
+            self.muzzle_velocity, self.max_range
+        ));
+        out.push_str(" RANGE      QE     TIME    DRIFT   IMPACT   ANGLE    MAX.\n");
+        out.push_str("                                   VEL.  OF FALL  ORDINATE\n");
+        out.push_str("  (M)     (MIL)     (S)      (M)   (M/S)    (MIL)     (M)\n\n");
+        for row in &self.rows {
+            out.push_str(&format!(
+                "{:6.0} {:7.1} {:7.2} {:8.2} {:7.1} {:7.1} {:8.1}\n",
+                row.range, row.quadrant_elevation, row.time, row.drift,
+                row.impact_velocity, row.angle_of_fall, row.max_ordinate
+            ));
+        }
+        out
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "muzzle_velocity_mps,range_m,quadrant_elevation_mil,time_s,drift_m,impact_velocity_mps,angle_of_fall_mil,max_ordinate_m\n",
+        );
+        for row in &self.rows {
+            out.push_str(&format!(
+                "{},{},{:.2},{:.3},{:.3},{:.2},{:.2},{:.2}\n",
+                self.muzzle_velocity, row.range, row.quadrant_elevation, row.time, row.drift,
+                row.impact_velocity, row.angle_of_fall, row.max_ordinate
+            ));
+        }
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<table class=\"qe-table\">\n");
+        out.push_str(&format!(
+            "<caption>Charge {:.1} m/s, maximum range {:.0} m</caption>\n",
+            self.muzzle_velocity, self.max_range
+        ));
+        out.push_str(
+            "<tr><th>Range (m)</th><th>QE (mil)</th><th>Time (s)</th><th>Drift (m)</th>\
+             <th>Impact velocity (m/s)</th><th>Angle of fall (mil)</th><th>Max ordinate (m)</th></tr>\n",
+        );
+        for row in &self.rows {
+            out.push_str(&format!(
+                "<tr><td>{:.0}</td><td>{:.1}</td><td>{:.2}</td><td>{:.2}</td><td>{:.1}</td><td>{:.1}</td><td>{:.1}</td></tr>\n",
+                row.range, row.quadrant_elevation, row.time, row.drift,
+                row.impact_velocity, row.angle_of_fall, row.max_ordinate
+            ));
+        }
+        out.push_str("</table>\n");
+        out
+    }
+}
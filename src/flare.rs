@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectileInput;
+
+/// Mach number at which flare wave drag starts to build up, McCoy's
+/// boattail onset. It rises linearly to the sonic value by Mach 1.
+const ONSET_MACH: f64 = 0.85;
+
+/// Flare half-angle (degrees) above which the shock at the flare shoulder
+/// separates the boundary layer ahead of the flare.
+const SEPARATION_ANGLE: f64 = 15.0;
+
+/// Afterbody drag of a conical flare tail at one Mach number: the flare
+/// wave drag (CDBT), the skin friction of the flare frustum (part of CDSF)
+/// and the drag of the expanded base (CDB).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlareDrag {
+    pub mach: f64,
+    pub cdbt: f64,
+    pub cdsf_flare: f64,
+    pub cdb: f64,
+    pub pb_pinf: f64,
+}
+
+impl ProjectileInput {
+    /// Whether the afterbody is a conical flare: a tail cone opening to a
+    /// base wider than the body.
+    pub fn is_flare(&self) -> bool {
+        self.boattail_length > 0.0 && self.base_diameter > 1.0
+    }
+
+    /// Lateral area of the flare frustum in square calibers, 0 without a
+    /// flare.
+    pub fn flare_wetted_area(&self) -> f64 {
+        if !self.is_flare() {
+            return 0.0;
+        }
+        let rise = 0.5 * (self.base_diameter - 1.0);
+        let slant = (self.boattail_length * self.boattail_length + rise * rise).sqrt();
+        std::f64::consts::PI * 0.5 * (1.0 + self.base_diameter) * slant
+    }
+
+    /// Wetted area of the afterbody in square calibers: McCoy's cylinder
+    /// from the nose to the base, with a flare taking its own frustum in
+    /// place of the cylinder over the flare length. McCoy's value of pi is
+    /// kept so that other bodies reproduce his CDSF.
+    #[allow(clippy::approx_constant)]
+    pub fn afterbody_wetted_area(&self) -> f64 {
+        let afterbody = self.total_length - self.nose_length;
+        if self.is_flare() {
+            3.1416 * (afterbody - self.boattail_length) + self.flare_wetted_area()
+        } else {
+            3.1416 * afterbody
+        }
+    }
+
+    /// Flare wave drag at `mach`. Supersonic the flare carries the pressure
+    /// of a cone of its half-angle (Fleeman's fit to cone wave drag) over
+    /// the annulus it adds to the frontal area; the flare turns the flow
+    /// along the body just as a cone turns the free stream.
+    pub fn flare_wave_drag_at(&self, mach: f64) -> f64 {
+        if !self.is_flare() || mach <= ONSET_MACH {
+            return 0.0;
+        }
+        let angle = (-self.boattail_angle()).to_radians();
+        let annulus = self.base_diameter * self.base_diameter - 1.0;
+        let cone = |mach: f64| (1.59 + 1.83 / (mach * mach)) * angle.powf(1.69) * annulus;
+        if mach >= 1.0 {
+            cone(mach)
+        } else {
+            cone(1.0) * (mach - ONSET_MACH) / (1.0 - ONSET_MACH)
+        }
+    }
+
+    /// Flare afterbody drag at each Mach number of the drag table.
+    pub fn flare_drag(&self) -> Vec<FlareDrag> {
+        let area = self.flare_wetted_area();
+        self.calculate_drag_coefficients()
+            .iter()
+            .map(|row| FlareDrag {
+                mach: row.mach,
+                cdbt: row.cdbt,
+                cdsf_flare: 1.2732 * self.afterbody_friction_at(row.mach) * area,
+                cdb: row.cdb,
+                pb_pinf: row.pb_pinf,
+            })
+            .collect()
+    }
+
+    /// Warns when the flare is steep enough to separate the flow ahead of
+    /// it.
+    pub fn flare_diagnostics(&self) -> Vec<String> {
+        let mut diagnostics = Vec::new();
+        if self.is_flare() && -self.boattail_angle() > SEPARATION_ANGLE {
+            diagnostics.push(format!(
+                "FLARE HALF-ANGLE ABOVE {} DEG. FLOW MAY SEPARATE AHEAD OF THE FLARE. CDBT MAY BE INCORRECT.",
+                SEPARATION_ANGLE
+            ));
+        }
+        diagnostics
+    }
+}
@@ -0,0 +1,347 @@
+use serde::{Deserialize, Serialize};
+
+use crate::nose_tip::NoseTip;
+use crate::ProjectileInput;
+
+/// Number of axial slices used when integrating over the body contour.
+const SLICES: usize = 2000;
+
+/// Relative error in McCoy's nose wetted area beyond which a secant ogive
+/// is flagged.
+const WETTED_AREA_TOLERANCE: f64 = 0.05;
+
+/// The nose ogive as a designer specifies it. Radii in calibers (`None`
+/// for a cone), the junction angle between the nose and the cylinder in
+/// degrees (0 for a tangent ogive) and nose wetted areas in square
+/// calibers, exact and as approximated in `calculate_drag_coefficients`.
+/// `poor_approximation` marks a secant ogive whose McCoy wetted area is
+/// off by more than 5%.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct OgiveGeometry {
+    pub rt_r: f64,
+    pub tangent_radius: f64,
+    pub ogive_radius: Option<f64>,
+    pub junction_angle: f64,
+    pub wetted_area: f64,
+    pub mccoy_wetted_area: f64,
+    pub poor_approximation: bool,
+}
+
+/// Ogive radius (calibers) of a tangent ogive of the given nose length and
+/// meplat diameter.
+pub fn tangent_ogive_radius(nose_length: f64, meplat_diameter: f64) -> f64 {
+    let h = 0.5 * (1.0 - meplat_diameter);
+    (nose_length * nose_length + h * h) / (2.0 * h)
+}
+
+/// RT/R for an ogive of radius `ogive_radius` calibers, as designers give
+/// it (e.g. a 7 caliber tangent or 12 caliber secant ogive). Radii below
+/// the tangent radius would bulge beyond the body and are rejected.
+pub fn rt_r_from_ogive_radius(nose_length: f64, meplat_diameter: f64, ogive_radius: f64) -> Result<f64, String> {
+    if nose_length <= 0.0 || !(0.0..1.0).contains(&meplat_diameter) {
+        return Err("Nose length must be positive and the meplat below one caliber".to_string());
+    }
+    let tangent = tangent_ogive_radius(nose_length, meplat_diameter);
+    if ogive_radius < tangent * (1.0 - 1e-9) {
+        return Err(format!(
+            "Ogive radius {:.3} cal is below the tangent radius {:.3} cal for this nose",
+            ogive_radius, tangent
+        ));
+    }
+    Ok((tangent / ogive_radius).min(1.0))
+}
+
+/// Uniform-density mass properties of the body described by a
+/// `ProjectileInput`. Lengths in calibers, inertias in kg*m^2.
+#[derive(Debug, Clone, Copy)]
+pub struct MassProperties {
+    pub volume: f64,
+    pub cg_location: f64,
+    pub axial_inertia: f64,
+    pub transverse_inertia: f64,
+}
+
+/// One piece of the nose contour in calibers, from `start` to `end` (x, r):
+/// the circular arc `arc` (centre x, centre r, radius) or, for `None`, a
+/// straight line.
+#[derive(Debug, Clone, Copy)]
+pub struct NoseSegment {
+    pub start: (f64, f64),
+    pub end: (f64, f64),
+    pub arc: Option<(f64, f64, f64)>,
+}
+
+impl NoseSegment {
+    /// Segment from `start` to `end` with headshape `rt_r`: an arc of the
+    /// tangent radius for the rise between them divided by RT/R, or a
+    /// straight line for RT/R = 0. The centre lies on the perpendicular
+    /// bisector of the chord, on the side away from the body.
+    fn new(start: (f64, f64), end: (f64, f64), rt_r: f64) -> NoseSegment {
+        let length = end.0 - start.0;
+        let rise = end.1 - start.1;
+        let arc = (rt_r > 0.0).then(|| {
+            let chord = (length * length + rise * rise).sqrt();
+            let radius = ((length * length + rise * rise) / (2.0 * rise) / rt_r).max(0.5 * chord);
+            let offset = (radius * radius - 0.25 * chord * chord).sqrt();
+            let xc = start.0 + 0.5 * length + offset * rise / chord;
+            let yc = 0.5 * (start.1 + end.1) - offset * length / chord;
+            (xc, yc, radius)
+        });
+        NoseSegment { start, end, arc }
+    }
+
+    /// Radius (calibers) at `x` within the segment.
+    pub fn radius_at(&self, x: f64) -> f64 {
+        match self.arc {
+            Some((xc, yc, radius)) => {
+                let dx = x - xc;
+                yc + (radius * radius - dx * dx).max(0.0).sqrt()
+            }
+            None => self.start.1 + (self.end.1 - self.start.1) * (x - self.start.0) / (self.end.0 - self.start.0),
+        }
+    }
+
+    /// Slope dr/dx of the contour at `x` within the segment.
+    pub fn slope_at(&self, x: f64) -> f64 {
+        match self.arc {
+            Some((xc, yc, _)) => (xc - x) / (self.radius_at(x) - yc),
+            None => (self.end.1 - self.start.1) / (self.end.0 - self.start.0),
+        }
+    }
+}
+
+impl ProjectileInput {
+    /// Station and diameter (calibers) where the main ogive starts: the
+    /// meplat, or the base of the tip segment of a compound nose.
+    pub fn ogive_start(&self) -> (f64, f64) {
+        match self.compound_nose {
+            Some(tip) => (tip.tip_length, tip.tip_diameter),
+            None => (0.0, self.meplat_diameter),
+        }
+    }
+
+    /// Nose contour from the meplat to the shoulder.
+    pub fn nose_segments(&self) -> Vec<NoseSegment> {
+        let tip = (0.0, 0.5 * self.meplat_diameter);
+        let shoulder = (self.nose_length, 0.5);
+        match self.compound_nose {
+            Some(nose) => {
+                let joint = (nose.tip_length, 0.5 * nose.tip_diameter);
+                vec![NoseSegment::new(tip, joint, nose.tip_rt_r), NoseSegment::new(joint, shoulder, self.rt_r)]
+            }
+            None => vec![NoseSegment::new(tip, shoulder, self.rt_r)],
+        }
+    }
+
+    /// Ogive radius (calibers) of a tangent main ogive with this nose
+    /// length and meplat diameter.
+    pub fn tangent_ogive_radius(&self) -> f64 {
+        let (x, diameter) = self.ogive_start();
+        tangent_ogive_radius(self.nose_length - x, diameter)
+    }
+
+    /// Actual ogive radius (calibers) implied by RT/R. A cone (RT/R = 0)
+    /// returns infinity.
+    pub fn ogive_radius(&self) -> f64 {
+        if self.rt_r <= 0.0 {
+            f64::INFINITY
+        } else {
+            self.tangent_ogive_radius() / self.rt_r
+        }
+    }
+
+    /// Centre (x, r) and radius of the main ogive arc in calibers, or
+    /// `None` for a cone.
+    pub fn ogive_arc(&self) -> Option<(f64, f64, f64)> {
+        self.nose_segments().last().and_then(|segment| segment.arc)
+    }
+
+    /// Ogive radius, junction angle and nose wetted area, with a check of
+    /// McCoy's wetted-area approximation (the `d5` and `s1` terms of the
+    /// skin friction) against the exact area of the arc.
+    // McCoy's 1.5708 is kept as written so the area matches the drag code.
+    #[allow(clippy::approx_constant)]
+    pub fn ogive_geometry(&self) -> OgiveGeometry {
+        let l = self.nose_length;
+        let tip = 0.5 * self.meplat_diameter;
+        let (x0, d0) = self.ogive_start();
+        let slope = match self.ogive_arc() {
+            Some((xc, yc, _)) => (xc - l) / (0.5 - yc),
+            None => (0.5 - 0.5 * d0) / (l - x0),
+        };
+
+        let dx = l / SLICES as f64;
+        let mut wetted_area = 0.0;
+        let mut r0 = tip;
+        for i in 1..=SLICES {
+            let r1 = if i == SLICES { 0.5 } else { self.radius_at(i as f64 * dx) };
+            wetted_area += std::f64::consts::PI * (r0 + r1) * (dx * dx + (r1 - r0) * (r1 - r0)).sqrt();
+            r0 = r1;
+        }
+        // As in calculate_drag_coefficients.
+        let d5 = 1.0 + (0.333 + 0.02 / (l * l)) * self.rt_r;
+        let mccoy_wetted_area = 1.5708 * l * d5 * (1.0 + 1.0 / (8.0 * l * l));
+
+        let radius = self.ogive_radius();
+        let secant = self.rt_r > 0.0 && self.rt_r < 1.0;
+        OgiveGeometry {
+            rt_r: self.rt_r,
+            tangent_radius: self.tangent_ogive_radius(),
+            ogive_radius: radius.is_finite().then_some(radius),
+            junction_angle: slope.atan().to_degrees(),
+            wetted_area,
+            mccoy_wetted_area,
+            poor_approximation: secant && (mccoy_wetted_area / wetted_area - 1.0).abs() > WETTED_AREA_TOLERANCE,
+        }
+    }
+
+    /// Boattail half-angle in degrees (negative for a flare), 0 without a
+    /// boattail.
+    pub fn boattail_angle(&self) -> f64 {
+        if self.boattail_length <= 0.0 {
+            0.0
+        } else {
+            ((1.0 - self.base_diameter) / (2.0 * self.boattail_length)).atan().to_degrees()
+        }
+    }
+
+    /// Body radius (calibers) at `x` calibers aft of the nose tip. The nose
+    /// is made of `nose_segments`, the afterbody a cylinder and the boattail
+    /// a straight cone, or the rebate, arc and cone of a shaped boattail.
+    /// A sphere is a circle of one caliber.
+    pub fn radius_at(&self, x: f64) -> f64 {
+        if x < 0.0 || x > self.total_length {
+            return 0.0;
+        }
+
+        if self.is_sphere() {
+            return (0.25 - (x - 0.5) * (x - 0.5)).max(0.0).sqrt();
+        }
+
+        if x < self.nose_length {
+            let segments = self.nose_segments();
+            let segment = segments.iter().find(|segment| x <= segment.end.0).unwrap_or(&segments[0]);
+            return segment.radius_at(x);
+        }
+
+        let boattail_start = self.total_length - self.boattail_length;
+        if self.boattail_length > 0.0 && x > boattail_start {
+            return self.boattail_radius_at(x - boattail_start);
+        }
+
+        0.5
+    }
+
+    /// Mass properties of a solid of uniform density with the given mass (kg).
+    pub fn mass_properties(&self, mass: f64) -> MassProperties {
+        let dx = self.total_length / SLICES as f64;
+        let mut volume = 0.0;
+        let mut moment = 0.0;
+        let mut axial = 0.0;
+
+        for i in 0..SLICES {
+            let x = (i as f64 + 0.5) * dx;
+            let r = self.radius_at(x);
+            let dv = std::f64::consts::PI * r * r * dx;
+            volume += dv;
+            moment += x * dv;
+            axial += 0.5 * r * r * dv;
+        }
+
+        let cg = moment / volume;
+        let mut transverse = 0.0;
+        for i in 0..SLICES {
+            let x = (i as f64 + 0.5) * dx;
+            let r = self.radius_at(x);
+            let dv = std::f64::consts::PI * r * r * dx;
+            transverse += (0.25 * r * r + (x - cg) * (x - cg)) * dv;
+        }
+
+        // Convert from caliber units to SI using the reference diameter.
+        let d = self.ref_diameter / 1000.0;
+        let density = mass / volume;
+        MassProperties {
+            volume,
+            cg_location: cg,
+            axial_inertia: density * axial * d * d,
+            transverse_inertia: density * transverse * d * d,
+        }
+    }
+
+    /// Center of gravity (calibers from the nose): the entered value, or the
+    /// uniform-density centroid when it was left at 0.
+    pub fn effective_cg(&self) -> f64 {
+        if self.cg_location > 0.0 {
+            self.cg_location
+        } else {
+            self.mass_properties(1.0).cg_location
+        }
+    }
+
+    /// Inputs that do not describe a real body. Unlike the legacy warnings,
+    /// which mark shapes outside McCoy's data, these make the named
+    /// coefficients meaningless rather than merely less accurate.
+    pub fn consistency_diagnostics(&self) -> Vec<String> {
+        let mut diagnostics = Vec::new();
+
+        if self.nose_length + self.boattail_length > self.total_length {
+            diagnostics.push("NOSE PLUS BOATTAIL LONGER THAN THE PROJECTILE. CDBT AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.meplat_diameter > 1.0 {
+            diagnostics.push("MEPLAT WIDER THAN THE BODY. CDH AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.bands.is_empty() && self.band_diameter < 1.0 {
+            diagnostics.push("BAND DIAMETER BELOW ONE CALIBER. CDBND AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if !self.bands.is_empty() && !self.bands_fit() {
+            diagnostics.push("BANDS OVERLAP, ARE BELOW ONE CALIBER OR LIE OFF THE CYLINDER. CDBND AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.rt_r) {
+            diagnostics.push("RT/R OUTSIDE 0 TO 1. SUPERSONIC CDH, CDBT AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.boattail_length <= 0.0 && self.base_diameter != 1.0 {
+            diagnostics.push("BASE DIAMETER NOT 1 WITHOUT A BOATTAIL. CDB AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.compound_nose.is_some_and(|tip| !tip.fits(self.nose_length, self.meplat_diameter)) {
+            diagnostics.push("COMPOUND NOSE TIP DOES NOT FIT THE NOSE. CDH AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if let Some(NoseTip::Radiused { radius }) = self.nose_tip {
+            if radius < 0.5 * self.meplat_diameter {
+                diagnostics.push("TIP RADIUS BELOW HALF THE MEPLAT DIAMETER. CDH AND CD0 ARE MEANINGLESS.".to_string());
+            }
+        }
+        if self.tip_cavity.is_some_and(|cavity| !cavity.fits(self.meplat_diameter)) {
+            diagnostics.push("TIP CAVITY DOES NOT FIT THE MEPLAT. CDH AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.base_shape.is_some_and(|shape| !shape.fits(self.base_diameter)) {
+            diagnostics.push("BASE CAVITY OR EDGE RADIUS DOES NOT FIT THE BASE. CDB AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.base_bleed.is_some_and(|bleed| bleed.injection < 0.0 || bleed.burn_time < 0.0) {
+            diagnostics.push("NEGATIVE INJECTION PARAMETER OR BURN TIME. CDB AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.rocket_motor.is_some_and(|motor| !motor.fits(self.base_diameter)) {
+            diagnostics.push("ROCKET NOZZLE DOES NOT FIT THE BASE OR MOTOR INPUTS INVALID. CDB AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.rocket_motor.is_some() && self.base_bleed.is_some() {
+            diagnostics.push("BASE BLEED AND ROCKET MOTOR BOTH GIVEN. CDB AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.surface.is_some_and(|surface| !surface.is_valid()) {
+            diagnostics.push("GROOVE OR ENGRAVING DIMENSIONS NOT POSITIVE. CDGRV, CDENG AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if !self.boattail_shape_fits() {
+            diagnostics.push("BOATTAIL RADIUS OR REBATE DOES NOT FIT THE BOATTAIL. CDBT AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.fins.is_some_and(|fins| !fins.is_valid()) {
+            diagnostics.push("FIN DIMENSIONS NOT POSITIVE OR FINS THICKER THAN THEIR CHORD. CDFSF, CDFW, CDFB AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        if self.fins.is_some_and(|fins| fins.root_chord > self.total_length) {
+            diagnostics.push("FIN ROOT CHORD LONGER THAN THE PROJECTILE. CDFSF, CDFW, CDFB AND CD0 ARE MEANINGLESS.".to_string());
+        }
+        // A center of gravity of 0 means "not given" and is computed instead.
+        if self.cg_location < 0.0 || self.cg_location > self.total_length {
+            diagnostics.push("CENTER OF GRAVITY OUTSIDE THE BODY. CMA, CMQ AND THE YAW DRAG ARE MEANINGLESS.".to_string());
+        }
+
+        diagnostics
+    }
+}
@@ -1,6 +1,34 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 
+pub mod aero;
+pub mod atmosphere;
+pub mod bands;
+pub mod base_bleed;
+pub mod base_shape;
+pub mod boattail_shape;
+pub mod compound_nose;
+pub mod drawing;
+pub mod envelope;
+pub mod fins;
+pub mod firing_table;
+pub mod flare;
+pub mod geometry;
+pub mod model;
+pub mod nose_tip;
+pub mod profile_fit;
+pub mod retardation;
+pub mod rocket_motor;
+pub mod silhouette;
+pub mod sphere;
+pub mod tip_cavity;
+pub mod standard_drag;
+pub mod surface;
+pub mod stl_import;
+pub mod trajectory;
+pub mod truing;
+pub mod yaw_drag;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum BoundaryLayer {
     LaminarLaminar,
@@ -9,6 +37,7 @@ pub enum BoundaryLayer {
 }
 
 impl BoundaryLayer {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_uppercase().as_str() {
             "L/L" => Some(BoundaryLayer::LaminarLaminar),
@@ -27,6 +56,15 @@ impl BoundaryLayer {
     }
 }
 
+/// Drag model of the projectile: McCoy's slender-body correlations, or the
+/// sphere drag correlation for round balls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ProjectileType {
+    #[default]
+    Slender,
+    Sphere,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProjectileInput {
     pub ref_diameter: f64,
@@ -40,15 +78,47 @@ pub struct ProjectileInput {
     pub cg_location: f64,
     pub boundary_layer: BoundaryLayer,
     pub identification: String,
+    #[serde(default)]
+    pub compound_nose: Option<compound_nose::CompoundNose>,
+    #[serde(default)]
+    pub nose_tip: Option<nose_tip::NoseTip>,
+    #[serde(default)]
+    pub bands: Vec<bands::Band>,
+    #[serde(default)]
+    pub surface: Option<surface::SurfaceFeatures>,
+    #[serde(default)]
+    pub tip_cavity: Option<tip_cavity::TipCavity>,
+    #[serde(default)]
+    pub base_shape: Option<base_shape::BaseShape>,
+    #[serde(default)]
+    pub base_bleed: Option<base_bleed::BaseBleed>,
+    #[serde(default)]
+    pub rocket_motor: Option<rocket_motor::RocketMotor>,
+    #[serde(default)]
+    pub fins: Option<fins::FinSet>,
+    #[serde(default)]
+    pub boattail_shape: Option<boattail_shape::BoattailShape>,
+    #[serde(default)]
+    pub projectile_type: ProjectileType,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DragCoefficients {
     pub mach: f64,
     pub cd0: f64,
     pub cdh: f64,
     pub cdsf: f64,
     pub cdbnd: f64,
+    #[serde(default)]
+    pub cdgrv: f64,
+    #[serde(default)]
+    pub cdeng: f64,
+    #[serde(default)]
+    pub cdfsf: f64,
+    #[serde(default)]
+    pub cdfw: f64,
+    #[serde(default)]
+    pub cdfb: f64,
     pub cdbt: f64,
     pub cdb: f64,
     pub pb_pinf: f64,
@@ -69,6 +139,7 @@ pub struct InputSummary {
     pub rt_r: f64,
     pub boattail_length: f64,
     pub base_diameter: f64,
+    pub boattail_angle: f64,
     pub meplat_diameter: f64,
     pub band_diameter: f64,
     pub cg_location: f64,
@@ -76,8 +147,93 @@ pub struct InputSummary {
     pub identification: String,
 }
 
+/// McCoy's head drag at `mach` for a nose of length `nose_length`, meplat
+/// diameter `meplat_diameter` and headshape `rt_r`, all in calibers of the
+/// nose base: the pressure drag of the ogive or cone and that of the flat
+/// meplat face.
+#[allow(clippy::approx_constant)]
+pub(crate) fn head_drag(mach: f64, nose_length: f64, meplat_diameter: f64, rt_r: f64) -> (f64, f64) {
+    let t1 = (1.0 - meplat_diameter) / nose_length;
+    let m2 = mach * mach;
+    let c15 = (m2 - 1.0) / (2.4 * m2);
+
+    let p5 = if mach <= 1.0 {
+        (1.0 + 0.2 * m2).powf(3.5)
+    } else {
+        (1.2 * m2).powf(3.5) * (6.0 / (7.0 * m2 - 1.0)).powf(2.5)
+    };
+
+    let c16 = (1.122 * (p5 - 1.0) * meplat_diameter * meplat_diameter) / m2;
+
+    let c18 = if mach <= 0.91 {
+        0.0
+    } else if mach >= 1.41 {
+        0.85 * c16
+    } else {
+        (0.254 + 2.88 * c15) * c16
+    };
+
+    let c17 = if mach <= 1.0 {
+        let x2 = (1.0 + 0.552 * t1.powf(0.8)).powf(-0.5);
+        if mach <= x2 {
+            0.0
+        } else {
+            0.368 * t1.powf(1.8) + 1.6 * t1 * c15
+        }
+    } else {
+        let b = (m2 - 1.0).sqrt();
+
+        let s4 = 1.0 + 0.368 * t1.powf(1.85);
+        let z = if mach >= s4 { b } else { (s4 * s4 - 1.0).sqrt() };
+
+        let c11 = 0.7156 - 0.5313 * rt_r + 0.595 * rt_r * rt_r;
+        let c12 = 0.0796 + 0.0779 * rt_r;
+        let c13 = 1.587 + 0.049 * rt_r;
+        let c14 = 0.1122 + 0.1658 * rt_r;
+
+        let r4 = 1.0 / (z * z);
+        (c11 - c12 * t1 * t1) * r4 * (t1 * z).powf(c13 + c14 * t1)
+    };
+
+    (c17, c18)
+}
+
 impl ProjectileInput {
+    /// MCDRAG drag table, with any base bleed or rocket motor burning.
     pub fn calculate_drag_coefficients(&self) -> Vec<DragCoefficients> {
+        self.drag_coefficients(true)
+    }
+
+    /// McCoy's laminar and turbulent flat plate skin friction coefficients
+    /// at `mach` for a surface `length` calibers long.
+    #[allow(clippy::approx_constant)]
+    pub(crate) fn friction_coefficients_at(&self, mach: f64, length: f64) -> (f64, f64) {
+        let m2 = mach * mach;
+        let reynolds = 23296.3 * mach * length * self.ref_diameter;
+        let log_reynolds = reynolds.ln() * 0.4343;
+        let c7 = (1.328 / reynolds.sqrt()) * (1.0 + 0.12 * m2).powf(-0.12);
+        let c8 = (0.455 / log_reynolds.powf(2.58)) * (1.0 + 0.21 * m2).powf(-0.32);
+        (c7, c8)
+    }
+
+    /// Skin friction coefficient of the afterbody at `mach`, laminar only
+    /// for a laminar boundary layer throughout.
+    pub(crate) fn afterbody_friction_at(&self, mach: f64) -> f64 {
+        let (c7, c8) = self.friction_coefficients_at(mach, self.total_length);
+        match self.boundary_layer {
+            BoundaryLayer::LaminarLaminar => c7,
+            _ => c8,
+        }
+    }
+
+    // The numeric constants below are carried over verbatim from McCoy's
+    // BASIC listing, so clippy's "use the std constant" advice is ignored.
+    #[allow(clippy::approx_constant)]
+    pub(crate) fn drag_coefficients(&self, burning: bool) -> Vec<DragCoefficients> {
+        if self.is_sphere() {
+            return self.sphere_drag_coefficients();
+        }
+
         let mach_numbers = vec![
             0.5, 0.6, 0.7, 0.8, 0.85, 0.9, 0.925, 0.95, 0.975, 1.0,
             1.1, 1.2, 1.3, 1.4, 1.5, 1.6, 1.7, 1.8, 2.0, 2.2,
@@ -88,16 +244,13 @@ impl ProjectileInput {
 
         for &mach in &mach_numbers {
             let t1 = (1.0 - self.meplat_diameter) / self.nose_length;
+            let (boattail_length, t2) = self.boattail_cone();
             let m2 = mach * mach;
-            let reynolds = 23296.3 * mach * self.total_length * self.ref_diameter;
-            let log_reynolds = reynolds.ln() * 0.4343;
-            
-            let c7 = (1.328 / reynolds.sqrt()) * (1.0 + 0.12 * m2).powf(-0.12);
-            let c8 = (0.455 / log_reynolds.powf(2.58)) * (1.0 + 0.21 * m2).powf(-0.32);
+            let (c7, c8) = self.friction_coefficients_at(mach, self.total_length);
             
             let d5 = 1.0 + (0.333 + 0.02 / (self.nose_length * self.nose_length)) * self.rt_r;
             let s1 = 1.5708 * self.nose_length * d5 * (1.0 + 1.0 / (8.0 * self.nose_length * self.nose_length));
-            let s2 = 3.1416 * (self.total_length - self.nose_length);
+            let s2 = self.afterbody_wetted_area();
             let s3 = s1 + s2;
             
             let (c9, c10) = match self.boundary_layer {
@@ -115,107 +268,79 @@ impl ProjectileInput {
             };
             
             let cdsf = (c9 * s1 + c10 * s2) / s3;
+            let (cdgrv, cdeng) = self.surface_drag_at(mach, self.afterbody_friction_at(mach));
             
             let c15 = (m2 - 1.0) / (2.4 * m2);
             
-            let p5 = if mach <= 1.0 {
-                (1.0 + 0.2 * m2).powf(3.5)
-            } else {
-                (1.2 * m2).powf(3.5) * (6.0 / (7.0 * m2 - 1.0)).powf(2.5)
-            };
-            
-            let c16 = (1.122 * (p5 - 1.0) * self.meplat_diameter * self.meplat_diameter) / m2;
-            
-            let c18 = if mach <= 0.91 {
-                0.0
-            } else if mach >= 1.41 {
-                0.85 * c16
-            } else {
-                (0.254 + 2.88 * c15) * c16
-            };
-            
             let p2 = if mach < 1.0 {
                 1.0 / (1.0 + 0.1875 * m2 + 0.0531 * m2 * m2)
             } else {
                 1.0 / (1.0 + 0.2477 * m2 + 0.0345 * m2 * m2)
             };
             
+            // The boattail term raises base pressure as the afterbody narrows;
+            // the expanded base behind a flare gets the flat base value.
             let p4 = (1.0 + 0.09 * m2 * (1.0 - (-self.total_length + self.nose_length).exp())) 
-                     * (1.0 + 0.25 * m2 * (1.0 - self.base_diameter));
+                     * (1.0 + 0.25 * m2 * self.boattail_convergence().max(0.0));
             
-            let pb_pinf = (p2 * p4).max(0.0);
-            
-            let cdb = (1.4286 * (1.0 - pb_pinf) * self.base_diameter * self.base_diameter) / m2;
-            
-            let cdbnd = if mach < 0.95 {
-                mach.powf(12.5) * (self.band_diameter - 1.0)
-            } else {
-                (0.21 + 0.28 / m2) * (self.band_diameter - 1.0)
+            let (pb_pinf, cdb) = match self.rocket_motor.filter(|_| burning) {
+                Some(motor) => self.jet_on_base_drag(&motor, mach, (p2 * p4).max(0.0)),
+                None => {
+                    let pb_pinf = self.base_pressure_at(mach, (p2 * p4).max(0.0), burning);
+                    let base_diameter = self.effective_base_diameter();
+                    (pb_pinf, (1.4286 * (1.0 - pb_pinf) * base_diameter * base_diameter) / m2)
+                }
             };
             
+            let cdbnd = self.band_drag_at(mach);
+            
             let (cdh, cdbt) = if mach <= 1.0 {
-                let x2 = (1.0 + 0.552 * t1.powf(0.8)).powf(-0.5);
-                let c17 = if mach <= x2 {
-                    0.0
-                } else {
-                    0.368 * t1.powf(1.8) + 1.6 * t1 * c15
-                };
-                
                 let cdbt = if self.boattail_length <= 0.0 || mach <= 0.85 {
                     0.0
+                } else if self.is_flare() {
+                    self.flare_wave_drag_at(mach)
                 } else {
-                    let t2 = (1.0 - self.base_diameter) / (2.0 * self.boattail_length);
                     let t3 = 2.0 * t2 * t2 + t2 * t2 * t2;
-                    let e1 = (-2.0 * self.boattail_length).exp();
-                    let b4 = 1.0 - e1 + 2.0 * t2 * (e1 * (self.boattail_length + 0.5) - 0.5);
+                    let e1 = (-2.0 * boattail_length).exp();
+                    let b4 = 1.0 - e1 + 2.0 * t2 * (e1 * (boattail_length + 0.5) - 0.5);
                     2.0 * t3 * b4 * (1.0 / (0.564 + 1250.0 * c15 * c15))
                 };
                 
-                (c17 + c18, cdbt)
+                (self.head_drag_at(mach), cdbt)
             } else {
                 let b2 = m2 - 1.0;
                 let b = b2.sqrt();
                 
-                let s4 = 1.0 + 0.368 * t1.powf(1.85);
-                let z = if mach >= s4 { b } else { (s4 * s4 - 1.0).sqrt() };
-                
-                let c11 = 0.7156 - 0.5313 * self.rt_r + 0.595 * self.rt_r * self.rt_r;
-                let c12 = 0.0796 + 0.0779 * self.rt_r;
-                let c13 = 1.587 + 0.049 * self.rt_r;
-                let c14 = 0.1122 + 0.1658 * self.rt_r;
-                
-                let r4 = 1.0 / (z * z);
-                let c17 = (c11 - c12 * t1 * t1) * r4 * (t1 * z).powf(c13 + c14 * t1);
-                
                 let cdbt = if self.boattail_length <= 0.0 {
                     0.0
+                } else if self.is_flare() {
+                    self.flare_wave_drag_at(mach)
+                } else if mach <= 1.1 {
+                    let t3 = 2.0 * t2 * t2 + t2 * t2 * t2;
+                    let e1 = (-2.0 * boattail_length).exp();
+                    let b4 = 1.0 - e1 + 2.0 * t2 * (e1 * (boattail_length + 0.5) - 0.5);
+                    2.0 * t3 * b4 * (1.774 - 9.3 * c15)
                 } else {
-                    let t2 = (1.0 - self.base_diameter) / (2.0 * self.boattail_length);
-                    
-                    if mach <= 1.1 {
-                        let t3 = 2.0 * t2 * t2 + t2 * t2 * t2;
-                        let e1 = (-2.0 * self.boattail_length).exp();
-                        let b4 = 1.0 - e1 + 2.0 * t2 * (e1 * (self.boattail_length + 0.5) - 0.5);
-                        2.0 * t3 * b4 * (1.774 - 9.3 * c15)
-                    } else {
-                        let b3 = 0.85 / b;
-                        let a12 = (5.0 * t1) / (6.0 * b) + (0.5 * t1).powf(2.0) 
-                                  - (0.7435 / m2) * (t1 * mach).powf(1.6);
-                        let a11 = (1.0 - (0.6 * self.rt_r) / mach) * a12;
-                        let e2 = ((-1.1952 / mach) * (self.total_length - self.nose_length - self.boattail_length)).exp();
-                        let x3 = ((2.4 * m2 * m2 - 4.0 * b2) * t2 * t2) / (2.0 * b2 * b2);
-                        let a1 = a11 * e2 - x3 + (2.0 * t2) / b;
-                        let r5 = 1.0 / b3;
-                        let e3 = (-b3 * self.boattail_length).exp();
-                        let a2 = 1.0 - e3 + 2.0 * t2 * (e3 * (self.boattail_length + r5) - r5);
-                        4.0 * a1 * t2 * a2 * r5
-                    }
+                    let b3 = 0.85 / b;
+                    let a12 = (5.0 * t1) / (6.0 * b) + (0.5 * t1).powf(2.0) 
+                              - (0.7435 / m2) * (t1 * mach).powf(1.6);
+                    let a11 = (1.0 - (0.6 * self.rt_r) / mach) * a12;
+                    let e2 = ((-1.1952 / mach) * (self.total_length - self.nose_length - boattail_length)).exp();
+                    let x3 = ((2.4 * m2 * m2 - 4.0 * b2) * t2 * t2) / (2.0 * b2 * b2);
+                    let a1 = a11 * e2 - x3 + (2.0 * t2) / b;
+                    let r5 = 1.0 / b3;
+                    let e3 = (-b3 * boattail_length).exp();
+                    let a2 = 1.0 - e3 + 2.0 * t2 * (e3 * (boattail_length + r5) - r5);
+                    4.0 * a1 * t2 * a2 * r5
                 };
                 
-                (c17 + c18, cdbt)
+                (self.head_drag_at(mach), cdbt)
             };
+            let cdbt = cdbt + self.rebate_drag_at(mach, p2);
+            
+            let (cdfsf, cdfw, cdfb) = self.fin_drag_at(mach, p2);
             
-            let cd0 = cdh + cdsf + cdbnd + cdbt + cdb;
+            let cd0 = cdh + cdsf + cdbnd + cdgrv + cdeng + cdfsf + cdfw + cdfb + cdbt + cdb;
             
             results.push(DragCoefficients {
                 mach,
@@ -223,6 +348,11 @@ impl ProjectileInput {
                 cdh,
                 cdsf,
                 cdbnd,
+                cdgrv,
+                cdeng,
+                cdfsf,
+                cdfw,
+                cdfb,
                 cdbt,
                 cdb,
                 pb_pinf,
@@ -233,6 +363,10 @@ impl ProjectileInput {
     }
 
     pub fn get_diagnostics(&self) -> Vec<String> {
+        if self.is_sphere() {
+            return self.sphere_diagnostics();
+        }
+
         let mut diagnostics = Vec::new();
         
         if self.nose_length < 1.0 {
@@ -241,14 +375,25 @@ impl ProjectileInput {
         if self.meplat_diameter > 0.5 {
             diagnostics.push("NOSE TOO BLUNT. CDH IS TOO HIGH AT TRANSONIC AND SUPERSONIC SPEEDS.".to_string());
         }
-        if self.boattail_length >= 1.5 {
+        if self.boattail_length >= 1.5 && !self.is_flare() {
             diagnostics.push("BOATTAIL TOO LONG. CDBT AND CDB MAY BE INCORRECT.".to_string());
         }
         if self.base_diameter < 0.65 {
             diagnostics.push("BOATTAIL TOO STEEP. CDBT AND CDB MAY BE INCORRECT.".to_string());
-        } else if self.base_diameter > 1.35 {
-            diagnostics.push("CONICAL FLARE TAIL TOO STEEP. CDBT AND CDB MAY BE INCORRECT.".to_string());
         }
+        let ogive = self.ogive_geometry();
+        if ogive.poor_approximation {
+            diagnostics.push(format!(
+                "SECANT OGIVE. MCCOY NOSE WETTED AREA OFF BY {:.0}%. CDSF MAY BE INCORRECT.",
+                100.0 * (ogive.mccoy_wetted_area / ogive.wetted_area - 1.0).abs()
+            ));
+        }
+        diagnostics.extend(self.tip_diagnostics());
+        diagnostics.extend(self.cavity_diagnostics());
+        diagnostics.extend(self.bleed_diagnostics());
+        diagnostics.extend(self.flare_diagnostics());
+        diagnostics.extend(self.fin_diagnostics());
+        diagnostics.extend(self.consistency_diagnostics());
         
         diagnostics
     }
@@ -259,6 +404,12 @@ pub struct McDragCalculator {
     current_input: Option<ProjectileInput>,
 }
 
+impl Default for McDragCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[wasm_bindgen]
 impl McDragCalculator {
     #[wasm_bindgen(constructor)]
@@ -296,6 +447,7 @@ impl McDragCalculator {
                         rt_r: input.rt_r,
                         boattail_length: input.boattail_length,
                         base_diameter: input.base_diameter,
+                        boattail_angle: input.effective_boattail_angle(),
                         meplat_diameter: input.meplat_diameter,
                         band_diameter: input.band_diameter,
                         cg_location: input.cg_location,
@@ -313,6 +465,221 @@ impl McDragCalculator {
         }
     }
 
+    #[wasm_bindgen]
+    pub fn trajectory(&self, params_json: &str) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+        let params = serde_json::from_str::<trajectory::TrajectoryInput>(params_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid trajectory input: {}", e)))?;
+        let card = trajectory::range_card(input, &params).map_err(|e| JsValue::from_str(&e))?;
+
+        serde_json::to_string(&card)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    #[wasm_bindgen]
+    pub fn firing_table(&self, request_json: &str) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+        let request = serde_json::from_str::<firing_table::FiringTableRequest>(request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid firing table request: {}", e)))?;
+        let table = firing_table::firing_table(input, &request.trajectory, request.click, request.wind_unit)
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        match request.format {
+            Some(format) => Ok(table.format(format)),
+            None => serde_json::to_string(&table)
+                .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn quadrant_elevation_tables(&self, request_json: &str) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+        let request = serde_json::from_str::<firing_table::QuadrantElevationRequest>(request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid quadrant elevation request: {}", e)))?;
+        let tables = firing_table::quadrant_elevation_tables(input, &request.trajectory, &request.charges)
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        match request.format {
+            Some(format) => Ok(tables.iter().map(|t| t.format(format)).collect::<Vec<_>>().join("\n")),
+            None => serde_json::to_string(&tables)
+                .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn true_drag_curve(&self, request_json: &str) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+        let request = serde_json::from_str::<truing::TruingRequest>(request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid truing request: {}", e)))?;
+        let result = truing::true_drag_curve(input, &request.trajectory, &request.observations, request.mode)
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    #[wasm_bindgen]
+    pub fn yaw_drag(&self) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+        let table = input.yaw_drag_coefficients(&input.calculate_drag_coefficients());
+
+        serde_json::to_string(&table)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    #[wasm_bindgen]
+    pub fn head_drag_split(&self) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+
+        serde_json::to_string(&input.head_drag_split())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    #[wasm_bindgen]
+    pub fn band_drag_breakdown(&self) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+
+        serde_json::to_string(&input.band_drag_breakdown())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    #[wasm_bindgen]
+    pub fn flare_drag(&self) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+
+        serde_json::to_string(&input.flare_drag())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    #[wasm_bindgen]
+    pub fn ogive_geometry(&self) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+
+        serde_json::to_string(&input.ogive_geometry())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// RT/R for an ogive radius in calibers.
+    #[wasm_bindgen]
+    pub fn rt_r_from_ogive_radius(nose_length: f64, meplat_diameter: f64, ogive_radius: f64) -> Result<f64, JsValue> {
+        geometry::rt_r_from_ogive_radius(nose_length, meplat_diameter, ogive_radius)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen]
+    pub fn validity_envelope(&self) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+        let table = input.validity_envelope(&input.calculate_drag_coefficients());
+
+        serde_json::to_string(&table)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    #[wasm_bindgen]
+    pub fn yaw_history(&self, request_json: &str) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+        let request = serde_json::from_str::<yaw_drag::YawHistoryRequest>(request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid yaw history request: {}", e)))?;
+        let history = input.yaw_history(&request.trajectory, request.first_max_yaw, request.distance)
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        serde_json::to_string(&history)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    #[wasm_bindgen]
+    pub fn retardation_table(&self, params_json: &str) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+        let params = serde_json::from_str::<retardation::RetardationInput>(params_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid retardation input: {}", e)))?;
+        let table = retardation::retardation_table(input, &params).map_err(|e| JsValue::from_str(&e))?;
+
+        serde_json::to_string(&table)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    #[wasm_bindgen]
+    pub fn export_profile(&self, request_json: &str) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+        let request = serde_json::from_str::<drawing::DrawingRequest>(request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid drawing request: {}", e)))?;
+
+        drawing::export_profile(input, &request).map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen]
+    pub fn export_model(&self, request_json: &str) -> Result<String, JsValue> {
+        let input = self.current_input.as_ref()
+            .ok_or_else(|| JsValue::from_str("No input data set"))?;
+        let request = serde_json::from_str::<model::ModelRequest>(request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid model request: {}", e)))?;
+
+        model::export_model(input, &request).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Fits a measured profile and makes the fitted case the current input.
+    #[wasm_bindgen]
+    pub fn import_profile(&mut self, request_json: &str) -> Result<String, JsValue> {
+        let request = serde_json::from_str::<profile_fit::ProfileImportRequest>(request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid profile import request: {}", e)))?;
+        let fit = profile_fit::import_profile(&request).map_err(|e| JsValue::from_str(&e))?;
+
+        let result = serde_json::to_string(&fit)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))?;
+        self.current_input = Some(fit.input);
+        Ok(result)
+    }
+
+    /// Fits an STL mesh (ASCII or binary) and makes the fitted case the
+    /// current input.
+    #[wasm_bindgen]
+    pub fn import_stl(&mut self, stl: &[u8], request_json: &str) -> Result<String, JsValue> {
+        let request = serde_json::from_str::<stl_import::MeshImportRequest>(request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid mesh import request: {}", e)))?;
+        let mesh = stl_import::import_stl(stl, &request).map_err(|e| JsValue::from_str(&e))?;
+
+        let result = serde_json::to_string(&mesh)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))?;
+        self.current_input = Some(mesh.fit.input);
+        Ok(result)
+    }
+
+    /// Fits a silhouette image (PNG or PPM/PGM) and makes the fitted case
+    /// the current input.
+    #[wasm_bindgen]
+    pub fn import_silhouette(&mut self, image: &[u8], request_json: &str) -> Result<String, JsValue> {
+        let request = serde_json::from_str::<silhouette::SilhouetteRequest>(request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid silhouette request: {}", e)))?;
+        let fit = silhouette::import_silhouette(image, &request).map_err(|e| JsValue::from_str(&e))?;
+
+        let result = serde_json::to_string(&fit)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))?;
+        self.current_input = Some(fit.fit.input);
+        Ok(result)
+    }
+
+    /// PNG of the image with the fitted shape drawn over it.
+    #[wasm_bindgen]
+    pub fn silhouette_overlay(&self, image: &[u8], request_json: &str) -> Result<Vec<u8>, JsValue> {
+        let request = serde_json::from_str::<silhouette::SilhouetteRequest>(request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid silhouette request: {}", e)))?;
+        let fit = silhouette::import_silhouette(image, &request).map_err(|e| JsValue::from_str(&e))?;
+        Ok(fit.overlay)
+    }
+
     #[wasm_bindgen]
     pub fn validate_boundary_layer(code: &str) -> bool {
         BoundaryLayer::from_str(code).is_some()
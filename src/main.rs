@@ -1,330 +1,981 @@
 use std::io::{self, Write};
 
-#[derive(Debug, Clone, Copy)]
-enum BoundaryLayer {
-    LaminarLaminar,
-    LaminarTurbulent,
-    TurbulentTurbulent,
-}
+use mcdrag::atmosphere::Atmosphere;
+use mcdrag::retardation::{self, RetardationInput, VelocityUnit};
+use mcdrag::rocket_motor::RocketMotor;
+use mcdrag::bands::{Band, BandKind};
+use mcdrag::base_bleed::BaseBleed;
+use mcdrag::base_shape::BaseShape;
+use mcdrag::boattail_shape::BoattailShape;
+use mcdrag::compound_nose::CompoundNose;
+use mcdrag::fins::{FinSet, LeadingEdge};
+use mcdrag::nose_tip::NoseTip;
+use mcdrag::drawing::{self, BandPosition, DrawingFormat, DrawingRequest};
+use mcdrag::model::{self, ModelFormat, ModelRequest};
+use mcdrag::profile_fit::{self, LengthUnit, ProfileFormat, ProfileImportRequest};
+use mcdrag::surface::SurfaceFeatures;
+use mcdrag::silhouette::{self, ImageScale, SilhouetteRequest};
+use mcdrag::tip_cavity::TipCavity;
+use mcdrag::stl_import::{self, MeshImportRequest};
+use mcdrag::geometry;
+use mcdrag::firing_table::{self, AngleUnit, ClickValue, TableFormat, WindUnit};
+use mcdrag::trajectory::{self, RangeCard, TrajectoryInput};
+use mcdrag::truing::{self, Observation, TruingMode, TruingResult};
+use mcdrag::{BoundaryLayer, DragCoefficients, ProjectileInput, ProjectileType};
+
+fn read_projectile_input() -> io::Result<ProjectileInput> {
+    println!("ENTER THE MCDRAG INPUTS, ONE QUANTITY AT A TIME.");
+    println!();
+
+    print!("ENTER PROJECTILE REFERENCE DIAMETER (MM): ");
+    io::stdout().flush()?;
+    let ref_diameter = read_float()?;
+    println!();
+
+    print!("ENTER TOTAL PROJECTILE LENGTH (CALIBERS): ");
+    io::stdout().flush()?;
+    let total_length = read_float()?;
+    println!();
+
+    print!("ENTER NOSE LENGTH (CALIBERS): ");
+    io::stdout().flush()?;
+    let nose_length = read_float()?;
+    println!();
+
+    print!("ENTER RT/R (HEADSHAPE PARAMETER), OR OGIVE RADIUS (CALIBERS) IF ABOVE 1: ");
+    io::stdout().flush()?;
+    let rt_r = read_float()?;
+    println!();
+
+    print!("ENTER BOATTAIL LENGTH (CALIBERS): ");
+    io::stdout().flush()?;
+    let boattail_length = read_float()?;
+    println!();
+
+    print!("ENTER BASE DIAMETER (CALIBERS): ");
+    io::stdout().flush()?;
+    let base_diameter = read_float()?;
+    println!();
+
+    print!("ENTER MEPLAT DIAMETER (CALIBERS): ");
+    io::stdout().flush()?;
+    let meplat_diameter = read_float()?;
+    println!();
 
-impl BoundaryLayer {
-    fn from_str(s: &str) -> Option<Self> {
-        match s.to_uppercase().as_str() {
-            "L/L" => Some(BoundaryLayer::LaminarLaminar),
-            "L/T" => Some(BoundaryLayer::LaminarTurbulent),
-            "T/T" => Some(BoundaryLayer::TurbulentTurbulent),
-            _ => None,
+    print!("ENTER ROTATING BAND DIAMETER (CALIBERS): ");
+    io::stdout().flush()?;
+    let band_diameter = read_float()?;
+    println!();
+
+    println!("[NOTE: CENTER OF GRAVITY LOCATION IS OPTIONAL; IF UNKNOWN, ENTER 0]");
+    println!();
+    print!("ENTER CENTER OF GRAVITY LOCATION (CALIBERS FROM NOSE): ");
+    io::stdout().flush()?;
+    let cg_location = read_float()?;
+    println!();
+
+    let boundary_layer = read_boundary_layer()?;
+
+    print!("ENTER PROJECTILE IDENTIFICATION: ");
+    io::stdout().flush()?;
+    let mut identification = String::new();
+    io::stdin().read_line(&mut identification)?;
+    let identification = identification.trim().to_string();
+
+    let mut input = ProjectileInput {
+        ref_diameter,
+        total_length,
+        nose_length,
+        rt_r,
+        boattail_length,
+        base_diameter,
+        meplat_diameter,
+        band_diameter,
+        cg_location,
+        boundary_layer,
+        identification,
+        compound_nose: None,
+        nose_tip: None,
+        bands: Vec::new(),
+        surface: None,
+        tip_cavity: None,
+        base_shape: None,
+        base_bleed: None,
+        rocket_motor: None,
+        fins: None,
+        boattail_shape: None,
+        projectile_type: ProjectileType::Slender,
+    };
+    println!();
+    if ask_yes_no("ENTER OPTIONAL SHAPE DETAILS? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        // A sphere keeps only the diameter and identification of the
+        // standard inputs.
+        if ask_yes_no("SPHERE OR ROUND BALL? (ENTER Y FOR YES, N FOR NO): ")? {
+            println!();
+            return Ok(ProjectileInput::sphere(input.ref_diameter, input.identification));
         }
+        read_shape_details(&mut input)?;
     }
 
-    fn to_str(&self) -> &str {
-        match self {
-            BoundaryLayer::LaminarLaminar => "L/L",
-            BoundaryLayer::LaminarTurbulent => "L/T",
-            BoundaryLayer::TurbulentTurbulent => "T/T",
+    // RT/R never exceeds 1, so a larger entry is an ogive radius, which
+    // needs the start of the main ogive to convert.
+    while input.rt_r > 1.0 {
+        let (start, diameter) = input.ogive_start();
+        match geometry::rt_r_from_ogive_radius(input.nose_length - start, diameter, input.rt_r) {
+            Ok(value) => {
+                println!("OGIVE RADIUS {:.3} CAL GIVES RT/R = {:.4}", input.rt_r, value);
+                println!();
+                input.rt_r = value;
+            }
+            Err(e) => {
+                println!("OGIVE RADIUS ERROR: {}", e);
+                input.rt_r = prompt_float("ENTER RT/R (HEADSHAPE PARAMETER), OR OGIVE RADIUS (CALIBERS) IF ABOVE 1: ")?;
+            }
         }
     }
-}
 
-#[derive(Debug)]
-struct ProjectileInput {
-    ref_diameter: f64,      // D1 - Reference diameter (mm)
-    total_length: f64,       // L1 - Total length (calibers)
-    nose_length: f64,        // L2 - Nose length (calibers)
-    rt_r: f64,              // R1 - RT/R headshape parameter
-    boattail_length: f64,    // L3 - Boattail length (calibers)
-    base_diameter: f64,      // D2 - Base diameter (calibers)
-    meplat_diameter: f64,    // D3 - Meplat diameter (calibers)
-    band_diameter: f64,      // D4 - Rotating band diameter (calibers)
-    cg_location: f64,        // X1 - Center of gravity location (calibers from nose)
-    boundary_layer: BoundaryLayer,
-    identification: String,
+    Ok(input)
 }
 
-#[derive(Debug)]
-struct DragCoefficients {
-    mach: f64,
-    cd0: f64,    // Total drag coefficient
-    cdh: f64,    // Head drag coefficient
-    cdsf: f64,   // Skin friction drag coefficient
-    cdbnd: f64,  // Band drag coefficient
-    cdbt: f64,   // Boattail drag coefficient
-    cdb: f64,    // Base drag coefficient
-    pb_pinf: f64, // Base pressure ratio
-}
-
-impl ProjectileInput {
-    fn new() -> io::Result<Self> {
-        println!("ENTER THE MCDRAG INPUTS, ONE QUANTITY AT A TIME.");
+/// Shape features beyond the MCDRAG inputs.
+fn read_shape_details(input: &mut ProjectileInput) -> io::Result<()> {
+    if ask_yes_no("COMPOUND NOSE (FUZE OR TIP SEGMENT)? (ENTER Y FOR YES, N FOR NO): ")? {
         println!();
-
-        print!("ENTER PROJECTILE REFERENCE DIAMETER (MM): ");
-        io::stdout().flush()?;
-        let ref_diameter = read_float()?;
+        let tip_length = prompt_float("ENTER TIP SEGMENT LENGTH (CALIBERS): ")?;
+        let tip_diameter = prompt_float("ENTER TIP SEGMENT BASE DIAMETER (CALIBERS): ")?;
+        let tip_rt_r = prompt_float("ENTER TIP SEGMENT RT/R (0 FOR A CONE): ")?;
+        input.compound_nose = Some(CompoundNose {
+            tip_length,
+            tip_diameter,
+            tip_rt_r,
+        });
+    }
+    if ask_yes_no("ROUNDED MEPLAT OR POLYMER TIP? (ENTER Y FOR YES, N FOR NO): ")? {
         println!();
-
-        print!("ENTER TOTAL PROJECTILE LENGTH (CALIBERS): ");
+        input.nose_tip = Some(if ask_yes_no("POLYMER TIP? (ENTER Y FOR YES, N FOR NO): ")? {
+            NoseTip::Polymer
+        } else {
+            println!();
+            let radius = prompt_float("ENTER TIP RADIUS (CALIBERS, 0 FOR HEMISPHERICAL): ")?;
+            NoseTip::Radiused {
+                radius: if radius > 0.0 { radius } else { 0.5 * input.meplat_diameter },
+            }
+        });
+    }
+    if ask_yes_no("OPEN TIP OR HOLLOW POINT CAVITY? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        let diameter = prompt_float("ENTER CAVITY DIAMETER (CALIBERS): ")?;
+        let depth = prompt_float("ENTER CAVITY DEPTH (CALIBERS): ")?;
+        input.tip_cavity = Some(TipCavity { diameter, depth });
+    }
+    if ask_yes_no("BANDS, OBTURATOR OR BOURRELET BY POSITION? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        println!("[NOTE: THESE REPLACE THE ROTATING BAND DIAMETER IN CDBND]");
+        println!();
+        let count = prompt_float("ENTER NUMBER OF BANDS: ")?.max(0.0) as usize;
+        for i in 1..=count {
+            let kind = loop {
+                print!("ENTER BAND {} TYPE (R = ROTATING, O = OBTURATOR, B = BOURRELET): ", i);
+                io::stdout().flush()?;
+                match BandKind::from_str(&read_line()?) {
+                    Some(kind) => break kind,
+                    None => println!("INCORRECT BAND TYPE. PLEASE TRY AGAIN."),
+                }
+            };
+            println!();
+            let location = prompt_float("ENTER BAND LOCATION (CALIBERS FROM NOSE): ")?;
+            let width = prompt_float("ENTER BAND WIDTH (CALIBERS): ")?;
+            let diameter = prompt_float("ENTER BAND DIAMETER (CALIBERS): ")?;
+            input.bands.push(Band {
+                kind,
+                location,
+                width,
+                diameter,
+            });
+        }
+    }
+    if ask_yes_no("RADIUSED OR REBATED BOATTAIL? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        let transition_radius = prompt_float("ENTER BOATTAIL TRANSITION RADIUS (CALIBERS, 0 FOR NONE): ")?;
+        let rebate_depth = prompt_float("ENTER REBATE STEP DEPTH (CALIBERS, 0 FOR NONE): ")?;
+        input.boattail_shape = Some(BoattailShape {
+            transition_radius,
+            rebate_depth,
+        });
+    }
+    if ask_yes_no("CUPPED, HOLLOW OR TRACER BASE, OR RADIUSED BASE EDGE? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        let mut shape = BaseShape::default();
+        print!("BASE CAVITY (C = CUP, H = HOLLOW BASE, T = TRACER, ENTER FOR NONE): ");
         io::stdout().flush()?;
-        let total_length = read_float()?;
+        let cavity = read_line()?.to_uppercase();
+        println!();
+        match cavity.as_str() {
+            "C" => shape.cup_depth = prompt_float("ENTER CUP DEPTH (CALIBERS): ")?,
+            "H" => {
+                shape.hollow_diameter = prompt_float("ENTER HOLLOW BASE DIAMETER (CALIBERS): ")?;
+                shape.hollow_depth = prompt_float("ENTER HOLLOW BASE DEPTH (CALIBERS): ")?;
+            }
+            "T" => shape.tracer_diameter = prompt_float("ENTER TRACER CAVITY DIAMETER (CALIBERS): ")?,
+            _ => {}
+        }
+        shape.edge_radius = prompt_float("ENTER BASE EDGE RADIUS (CALIBERS, 0 FOR SQUARE): ")?;
+        input.base_shape = Some(shape);
+    }
+    if ask_yes_no("BASE BLEED OR BURNING TRACER? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        println!("[NOTE: I = BLEED MASS FLOW / FREESTREAM MASS FLOW THROUGH THE BASE AREA]");
+        println!();
+        let injection = prompt_float("ENTER INJECTION PARAMETER I: ")?;
+        let burn_time = prompt_float("ENTER BURN TIME (SECONDS, 0 IF IT LASTS THE FLIGHT): ")?;
+        input.base_bleed = Some(BaseBleed { injection, burn_time });
+    }
+    if ask_yes_no("ROCKET ASSIST MOTOR? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        let exit_diameter = prompt_float("ENTER NOZZLE EXIT DIAMETER (CALIBERS): ")?;
+        let exit_mach = prompt_float("ENTER NOZZLE EXIT MACH NUMBER: ")?;
+        let pressure_ratio = prompt_float("ENTER CHAMBER TO AMBIENT PRESSURE RATIO: ")?;
+        let ignition_time = prompt_float("ENTER IGNITION DELAY (SECONDS): ")?;
+        let burn_time = prompt_float("ENTER BURN TIME (SECONDS): ")?;
+        input.rocket_motor = Some(RocketMotor {
+            exit_diameter,
+            exit_mach,
+            pressure_ratio,
+            ignition_time,
+            burn_time,
+        });
+    }
+    if ask_yes_no("CANNELURES, CRIMP GROOVES OR RIFLING ENGRAVING? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        let groove_count = prompt_float("ENTER NUMBER OF GROOVES (0 FOR NONE): ")?.max(0.0) as u32;
+        let (groove_depth, groove_width) = if groove_count > 0 {
+            (prompt_float("ENTER GROOVE DEPTH (CALIBERS): ")?, prompt_float("ENTER GROOVE WIDTH (CALIBERS): ")?)
+        } else {
+            (0.0, 0.0)
+        };
+        let groove_location = if groove_count > 0 {
+            prompt_float("ENTER FIRST GROOVE LOCATION (CALIBERS FROM NOSE TIP, 0 IF NOT KNOWN): ")?
+        } else {
+            0.0
+        };
+        let groove_spacing = if groove_count > 1 && groove_location > 0.0 {
+            prompt_float("ENTER GROOVE SPACING (CALIBERS): ")?
+        } else {
+            0.0
+        };
+        let engraving_count = prompt_float("ENTER NUMBER OF ENGRAVED LANDS (0 FOR NONE): ")?.max(0.0) as u32;
+        let engraving_depth = if engraving_count > 0 {
+            prompt_float("ENTER LAND ENGRAVING DEPTH (CALIBERS): ")?
+        } else {
+            0.0
+        };
+        input.surface = Some(SurfaceFeatures {
+            groove_count,
+            groove_depth,
+            groove_width,
+            groove_location,
+            groove_spacing,
+            engraving_count,
+            engraving_depth,
+        });
+    }
+    if ask_yes_no("FIN SET? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        let count = prompt_float("ENTER NUMBER OF FINS: ")?.max(0.0) as u32;
+        let span = prompt_float("ENTER FIN SPAN FROM THE BODY (CALIBERS): ")?;
+        let root_chord = prompt_float("ENTER FIN ROOT CHORD (CALIBERS): ")?;
+        let tip_chord = prompt_float("ENTER FIN TIP CHORD (CALIBERS): ")?;
+        let thickness = prompt_float("ENTER FIN THICKNESS (CALIBERS): ")?;
+        let edge = loop {
+            print!("ENTER LEADING EDGE (S = SQUARE, R = ROUNDED, W = WEDGE): ");
+            io::stdout().flush()?;
+            let edge = read_line()?.to_uppercase();
+            if ["S", "R", "W"].contains(&edge.as_str()) {
+                break edge;
+            }
+            println!("INCORRECT LEADING EDGE. PLEASE TRY AGAIN.");
+        };
         println!();
+        let leading_edge = match edge.as_str() {
+            "S" => LeadingEdge::Square,
+            "R" => LeadingEdge::Rounded,
+            _ => LeadingEdge::Wedge {
+                half_angle: prompt_float("ENTER WEDGE HALF-ANGLE NORMAL TO THE EDGE (DEG): ")?,
+            },
+        };
+        input.fins = Some(FinSet {
+            count,
+            span,
+            root_chord,
+            tip_chord,
+            thickness,
+            leading_edge,
+        });
+    }
+    Ok(())
+}
+
+fn read_boundary_layer() -> io::Result<BoundaryLayer> {
+    println!("FOR ALL LAMINAR BOUNDARY LAYER, CODE = L/L");
+    println!("FOR LAMINAR NOSE, TURBULENT AFTERBODY, CODE = L/T");
+    println!("FOR ALL TURBULENT BOUNDARY LAYER, CODE = T/T");
+    println!();
 
-        print!("ENTER NOSE LENGTH (CALIBERS): ");
+    let boundary_layer = loop {
+        print!("ENTER THE BOUNDARY LAYER CODE (L/L, L/T, OR T/T): ");
         io::stdout().flush()?;
-        let nose_length = read_float()?;
-        println!();
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim();
+        
+        if let Some(bl) = BoundaryLayer::from_str(input) {
+            break bl;
+        } else {
+            println!("INCORRECT BOUNDARY LAYER CODE. PLEASE TRY AGAIN.");
+        }
+    };
+    println!();
+    Ok(boundary_layer)
+}
 
-        print!("ENTER RT/R (HEADSHAPE PARAMETER): ");
+/// Fits the measured profile in `path` (CSV, DXF or STL by extension) and
+/// asks for the inputs an outline cannot give. Returns the case and the fit
+/// report, or `None` after reporting a failure.
+fn import_case(path: &str) -> io::Result<Option<(ProjectileInput, String)>> {
+    println!("IMPORTING MEASURED PROFILE {}", path);
+    println!();
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            println!("CANNOT READ {}: {}", path, e);
+            return Ok(None);
+        }
+    };
+    let extension = path.rsplit('.').next().unwrap_or("").to_uppercase();
+
+    let image = matches!(extension.as_str(), "PNG" | "PPM" | "PGM");
+    let mut units = LengthUnit::Millimeters;
+    let mut scale = ImageScale::ScaleBar { length: 1.0, pixels: 1.0 };
+    if image {
+        let diameter = prompt_float("ENTER REFERENCE DIAMETER (MM), OR 0 TO USE A SCALE BAR: ")?;
+        scale = if diameter > 0.0 {
+            ImageScale::ReferenceDiameter { diameter }
+        } else {
+            let length = prompt_float("ENTER SCALE BAR LENGTH (MM): ")?;
+            let pixels = prompt_float("ENTER SCALE BAR LENGTH (PIXELS): ")?;
+            ImageScale::ScaleBar { length, pixels }
+        };
+    } else {
+        print!("PROFILE UNITS (MM OR IN): ");
         io::stdout().flush()?;
-        let rt_r = read_float()?;
+        if read_line()?.to_uppercase() == "IN" {
+            units = LengthUnit::Inches;
+        }
         println!();
+    }
+    let cg_location = prompt_float("ENTER CENTER OF GRAVITY LOCATION (CALIBERS FROM NOSE, 0 IF UNKNOWN): ")?;
+    let boundary_layer = read_boundary_layer()?;
+    print!("ENTER PROJECTILE IDENTIFICATION: ");
+    io::stdout().flush()?;
+    let identification = read_line()?;
 
-        print!("ENTER BOATTAIL LENGTH (CALIBERS): ");
+    let result = if image {
+        let request = SilhouetteRequest {
+            scale,
+            boundary_layer,
+            identification,
+        };
+        silhouette::import_silhouette(&bytes, &request).and_then(|fit| {
+            let stem = path.rsplit_once('.').map_or(path, |(stem, _)| stem);
+            let overlay = format!("{}_overlay.png", stem);
+            std::fs::write(&overlay, &fit.overlay).map_err(|e| format!("cannot write {}: {}", overlay, e))?;
+            println!("FIT OVERLAY WRITTEN TO {}", overlay);
+            let report = fit.to_text();
+            Ok((fit.fit.input, report))
+        })
+    } else if extension == "STL" {
+        let request = MeshImportRequest {
+            units,
+            boundary_layer,
+            identification,
+        };
+        stl_import::import_stl(&bytes, &request).map(|mesh| {
+            let report = mesh.to_text();
+            (mesh.fit.input, report)
+        })
+    } else {
+        let request = ProfileImportRequest {
+            format: if extension == "DXF" { ProfileFormat::Dxf } else { ProfileFormat::Csv },
+            data: String::from_utf8_lossy(&bytes).into_owned(),
+            units,
+            boundary_layer,
+            identification,
+        };
+        profile_fit::import_profile(&request).map(|fit| {
+            let report = fit.to_text();
+            (fit.input, report)
+        })
+    };
+    match result {
+        Ok((mut input, report)) => {
+            input.cg_location = cg_location;
+            Ok(Some((input, report)))
+        }
+        Err(e) => {
+            println!("PROFILE IMPORT ERROR: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+fn prompt_float(prompt: &str) -> io::Result<f64> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let value = read_float()?;
+    println!();
+    Ok(value)
+}
+
+fn ask_yes_no(prompt: &str) -> io::Result<bool> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut response = String::new();
+    io::stdin().read_line(&mut response)?;
+    Ok(response.trim().to_uppercase() == "Y")
+}
+
+fn read_trajectory_input() -> io::Result<TrajectoryInput> {
+    println!("ENTER THE TRAJECTORY INPUTS, ONE QUANTITY AT A TIME.");
+    println!();
+
+    let mass = prompt_float("ENTER PROJECTILE MASS (GRAMS): ")?;
+    let muzzle_velocity = prompt_float("ENTER MUZZLE VELOCITY (M/S): ")?;
+    let twist = prompt_float("ENTER TWIST (CALIBERS PER TURN, NEGATIVE FOR LEFT HAND): ")?;
+    let range_step = prompt_float("ENTER RANGE STEP (M): ")?;
+    let max_range = prompt_float("ENTER MAXIMUM RANGE (M): ")?;
+    let sight_height = prompt_float("ENTER SIGHT HEIGHT ABOVE BORE (MM): ")?;
+    let zero_range = prompt_float("ENTER ZERO RANGE (M, 0 FOR A LEVEL BORE): ")?;
+    let wind_speed = prompt_float("ENTER WIND SPEED (M/S): ")?;
+    let wind_direction = prompt_float("ENTER WIND DIRECTION (DEGREES, 0 = HEADWIND, 90 = FROM RIGHT): ")?;
+    let latitude = prompt_float("ENTER LATITUDE (DEGREES, NEGATIVE FOR SOUTH): ")?;
+    let azimuth = prompt_float("ENTER FIRING AZIMUTH (DEGREES FROM NORTH): ")?;
+    let temperature = prompt_float("ENTER AIR TEMPERATURE (DEG C): ")?;
+    let pressure = prompt_float("ENTER STATION PRESSURE (HPA): ")?;
+    let humidity = prompt_float("ENTER RELATIVE HUMIDITY (PERCENT): ")?;
+
+    Ok(TrajectoryInput {
+        mass,
+        muzzle_velocity,
+        twist,
+        departure_angle: 0.0,
+        sight_height,
+        zero_range,
+        range_step,
+        max_range,
+        wind_speed,
+        wind_direction,
+        latitude,
+        azimuth,
+        atmosphere: Atmosphere {
+            temperature,
+            pressure,
+            humidity: humidity / 100.0,
+        },
+    })
+}
+
+fn read_line() -> io::Result<String> {
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+fn read_table_format() -> io::Result<Option<TableFormat>> {
+    print!("FIRING TABLE FORMAT (TEXT, CSV OR HTML; ENTER FOR NONE): ");
+    io::stdout().flush()?;
+    let format = match read_line()?.to_uppercase().as_str() {
+        "TEXT" => Some(TableFormat::Text),
+        "CSV" => Some(TableFormat::Csv),
+        "HTML" => Some(TableFormat::Html),
+        _ => None,
+    };
+    println!();
+    Ok(format)
+}
+
+/// Prints `text`, or writes it to a file when a name is entered.
+fn write_output(text: &str) -> io::Result<()> {
+    print!("ENTER OUTPUT FILE NAME (ENTER FOR SCREEN): ");
+    io::stdout().flush()?;
+    let path = read_line()?;
+    println!();
+    if path.is_empty() {
+        println!("{}", text);
+    } else {
+        std::fs::write(&path, text)?;
+        println!("WROTE {}", path);
+    }
+    Ok(())
+}
+
+fn run_firing_tables(input: &ProjectileInput, params: &TrajectoryInput) -> io::Result<()> {
+    let format = match read_table_format()? {
+        Some(format) => format,
+        None => return Ok(()),
+    };
+
+    print!("ENTER SIGHT CLICK VALUE (E.G. 0.25 MOA OR 0.1 MIL): ");
+    io::stdout().flush()?;
+    let click_text = read_line()?.to_uppercase();
+    println!();
+    let mut parts = click_text.split_whitespace();
+    let value = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0.25);
+    let click = match parts.next() {
+        Some("MIL") | Some("MILS") => ClickValue { value, unit: AngleUnit::Mil },
+        _ => ClickValue { value, unit: AngleUnit::Moa },
+    };
+    let wind_unit = match click.unit {
+        AngleUnit::Moa => WindUnit::TenMph,
+        AngleUnit::Mil => WindUnit::MetersPerSecond,
+    };
+
+    match firing_table::firing_table(input, params, click, wind_unit) {
+        Ok(table) => write_output(&table.format(format))?,
+        Err(e) => println!("FIRING TABLE ERROR: {}", e),
+    }
+
+    print!("ENTER CHARGE VELOCITIES FOR QE TABLES (M/S, COMMA SEPARATED; ENTER FOR NONE): ");
+    io::stdout().flush()?;
+    let charges: Vec<f64> = read_line()?
+        .split(',')
+        .filter_map(|v| v.trim().parse().ok())
+        .collect();
+    println!();
+    if !charges.is_empty() {
+        match firing_table::quadrant_elevation_tables(input, params, &charges) {
+            Ok(tables) => {
+                let text: Vec<String> = tables.iter().map(|t| t.format(format)).collect();
+                write_output(&text.join("\n"))?;
+            }
+            Err(e) => println!("QE TABLE ERROR: {}", e),
+        }
+    }
+
+    Ok(())
+}
+
+fn read_observations() -> io::Result<Vec<Observation>> {
+    println!("ENTER OBSERVATIONS, ONE PER LINE:");
+    println!("  V RANGE(M) VELOCITY(M/S)   FOR A CHRONOGRAPH READING");
+    println!("  D RANGE(M) DROP(CM)        FOR DROP BELOW THE LINE OF SIGHT");
+    println!("ENTER A BLANK LINE TO FINISH.");
+    println!();
+
+    let mut observations = Vec::new();
+    loop {
+        print!("OBSERVATION: ");
         io::stdout().flush()?;
-        let boattail_length = read_float()?;
+        let line = read_line()?.to_uppercase();
+        if line.is_empty() {
+            break;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let numbers: Vec<f64> = fields.iter().skip(1).filter_map(|v| v.parse().ok()).collect();
+        match (fields.first(), numbers.as_slice()) {
+            (Some(&"V"), [range, velocity]) => {
+                observations.push(Observation::Velocity { range: *range, velocity: *velocity })
+            }
+            (Some(&"D"), [range, drop]) => {
+                observations.push(Observation::Drop { range: *range, drop: drop / 100.0 })
+            }
+            _ => println!("INCORRECT OBSERVATION. PLEASE TRY AGAIN."),
+        }
+    }
+    println!();
+    Ok(observations)
+}
+
+fn print_truing_result(input: &ProjectileInput, result: &TruingResult) {
+    println!("TRUED DRAG CURVE");
+    println!();
+    println!("MUZZLE VELOCITY: {:7.1} M/S   IMPLIED BC: G1 {:5.3}   G7 {:5.3} (LB/IN2)",
+             result.muzzle_velocity, result.bc_g1, result.bc_g7);
+    println!();
+    println!("   MACH BAND       FACTOR");
+    for band in &result.factors {
+        let high = if band.mach_high.is_finite() {
+            format!("{:6.3}", band.mach_high)
+        } else {
+            "   MAX".to_string()
+        };
+        println!("{:6.3} - {}  {:7.4}", band.mach_low, high, band.factor);
+    }
+    println!();
+    println!("  RANGE   OBSERVED  PREDICTED  RESIDUAL");
+    for fit in &result.fits {
+        let (range, scale, unit) = match fit.observation {
+            Observation::Velocity { range, .. } => (range, 1.0, "M/S"),
+            Observation::Drop { range, .. } => (range, 100.0, "CM"),
+        };
+        println!("{:7.0} {:10.2} {:10.2} {:9.3} {}",
+                 range, (fit.predicted - fit.residual) * scale, fit.predicted * scale,
+                 fit.residual * scale, unit);
+    }
+    println!();
+    println!("   M      CD0   TRUED CD0");
+    println!();
+    for coeff in input.calculate_drag_coefficients() {
+        println!("{:6.3} {:7.3} {:9.3}", coeff.mach, coeff.cd0,
+                 trajectory::cd0_at(&result.drag, coeff.mach));
+    }
+}
+
+fn run_truing(input: &ProjectileInput, params: &TrajectoryInput) -> io::Result<()> {
+    let observations = read_observations()?;
+    print!("TRUING MODE (S = SCALE FACTOR, M = MACH DEPENDENT): ");
+    io::stdout().flush()?;
+    let mode = match read_line()?.to_uppercase().as_str() {
+        "M" => TruingMode::MachDependent,
+        _ => TruingMode::Scale,
+    };
+    println!();
+
+    match truing::true_drag_curve(input, params, &observations, mode) {
+        Ok(result) => print_truing_result(input, &result),
+        Err(e) => println!("TRUING ERROR: {}", e),
+    }
+    Ok(())
+}
+
+fn print_head_drag_split(input: &ProjectileInput) {
+    println!("HEAD DRAG BY NOSE SEGMENT");
+    println!();
+    println!("   M      CDH    CDH TIP  CDH OGIVE  TIP SHARE");
+    println!();
+    for row in input.head_drag_split() {
+        let share = if row.cdh > 0.0 { 100.0 * row.cdh_tip / row.cdh } else { 0.0 };
+        println!("{:6.3} {:7.3} {:8.3} {:9.3} {:9.1}%",
+                 row.mach, row.cdh, row.cdh_tip, row.cdh_ogive, share);
+    }
+}
+
+fn print_band_drag(input: &ProjectileInput) {
+    println!("BAND DRAG BY BAND");
+    println!();
+    for (i, band) in input.bands.iter().enumerate() {
+        println!("{:2}  {:9} AT {:.3} CAL, WIDTH {:.3} CAL, DIA {:.3} CAL",
+                 i + 1, band.kind.to_str(), band.location, band.width, band.diameter);
+    }
+    println!();
+    print!("   M     CDBND");
+    for i in 1..=input.bands.len() {
+        print!("  BAND {:2}", i);
+    }
+    println!();
+    println!();
+    for row in input.band_drag_breakdown() {
+        print!("{:6.3} {:8.4}", row.mach, row.cdbnd);
+        for cdbnd in &row.bands {
+            print!(" {:8.4}", cdbnd);
+        }
         println!();
+    }
+}
 
-        print!("ENTER BASE DIAMETER (CALIBERS): ");
-        io::stdout().flush()?;
-        let base_diameter = read_float()?;
+fn print_flare_drag(input: &ProjectileInput) {
+    println!("FLARE AFTERBODY DRAG");
+    println!();
+    println!("   M     CDBT  CDSF FLARE     CDB   PB/PINF");
+    println!();
+    for row in input.flare_drag() {
+        println!("{:6.3} {:7.3} {:11.4} {:7.3} {:9.3}",
+                 row.mach, row.cdbt, row.cdsf_flare, row.cdb, row.pb_pinf);
+    }
+}
+
+fn print_surface_drag(drag: &[DragCoefficients]) {
+    println!("SURFACE FEATURE DRAG");
+    println!();
+    println!("   M      CDSF    CDBND    CDGRV    CDENG   % OF CD0");
+    println!();
+    for row in drag {
+        let share = if row.cd0 > 0.0 { 100.0 * (row.cdgrv + row.cdeng) / row.cd0 } else { 0.0 };
+        println!("{:6.3} {:8.4} {:8.4} {:8.4} {:8.4} {:8.1}%",
+                 row.mach, row.cdsf, row.cdbnd, row.cdgrv, row.cdeng, share);
+    }
+}
+
+fn print_fin_drag(input: &ProjectileInput, drag: &[DragCoefficients]) {
+    if let Some(fins) = input.fins {
+        println!("FIN SET: {} FINS, {} LEADING EDGE, SWEEP {:.1} DEG, AREA {:.3} SQ CAL EACH",
+                 fins.count, fins.leading_edge.to_str(), fins.sweep().to_degrees(), fins.planform_area());
+    }
+    println!();
+    println!("   M     CDFSF     CDFW     CDFB   % OF CD0");
+    println!();
+    for row in drag {
+        let share = if row.cd0 > 0.0 { 100.0 * (row.cdfsf + row.cdfw + row.cdfb) / row.cd0 } else { 0.0 };
+        println!("{:6.3} {:8.4} {:8.4} {:8.4} {:8.1}%",
+                 row.mach, row.cdfsf, row.cdfw, row.cdfb, share);
+    }
+}
+
+fn print_power_off_drag(input: &ProjectileInput, drag: &[DragCoefficients]) {
+    if let Some(motor) = input.rocket_motor {
+        println!("ROCKET MOTOR: EXIT PRESSURE {:.3} x AMBIENT, JET ON FROM {:.1} TO {:.1} S",
+                 motor.exit_pressure_ratio(), motor.ignition_time, motor.ignition_time + motor.burn_time);
+    } else if let Some(bleed) = input.base_bleed {
+        println!("BASE BLEED: I = {:.4}, {:.0}% OF BASE DRAG RELIEVED WHILE BURNING",
+                 bleed.injection, 100.0 * input.bleed_relief());
+    }
+    println!();
+    println!("   M    CD0 ON  CD0 OFF   CDB ON  CDB OFF  PB/PINF ON  PB/PINF OFF");
+    println!();
+    for (on, off) in drag.iter().zip(input.power_off_drag_coefficients()) {
+        println!("{:6.3} {:7.3} {:8.3} {:8.3} {:8.3} {:10.3} {:12.3}",
+                 on.mach, on.cd0, off.cd0, on.cdb, off.cdb, on.pb_pinf, off.pb_pinf);
+    }
+}
+
+fn print_envelope(input: &ProjectileInput, drag: &[DragCoefficients]) {
+    let rows = input.validity_envelope(drag);
+    println!("   M      CD0    +/-%   CD0 LOW  CD0 HIGH   STATUS");
+    println!();
+    for row in &rows {
+        println!("{:6.3} {:7.3} {:6.1} {:8.3} {:8.3}   {}",
+                 row.mach, row.cd0, 100.0 * row.uncertainty,
+                 row.cd0_low, row.cd0_high, row.validity.to_str());
+    }
+    let mut limits: Vec<&String> = rows.iter().flat_map(|row| &row.limits).collect();
+    limits.sort();
+    limits.dedup();
+    if !limits.is_empty() {
         println!();
+        for limit in limits {
+            println!("{}", limit);
+        }
+    }
+}
 
-        print!("ENTER MEPLAT DIAMETER (CALIBERS): ");
-        io::stdout().flush()?;
-        let meplat_diameter = read_float()?;
+fn print_yaw_drag(input: &ProjectileInput, drag: &[DragCoefficients]) {
+    println!("   M      CD0    CDD2   CD(2 DEG) CD(5 DEG)");
+    println!();
+    for row in input.yaw_drag_coefficients(drag) {
+        println!("{:6.3} {:7.3} {:7.3} {:9.3} {:9.3}",
+                 row.mach, row.cd0, row.cd_delta2,
+                 input.drag_at_angle_of_attack(drag, row.mach, 2.0),
+                 input.drag_at_angle_of_attack(drag, row.mach, 5.0));
+    }
+}
+
+fn read_band_position(input: &ProjectileInput) -> io::Result<Option<BandPosition>> {
+    if input.band_diameter <= 1.0 || !input.bands.is_empty() {
+        return Ok(None);
+    }
+    let location = prompt_float("ENTER BAND LOCATION (CALIBERS FROM NOSE, 0 TO OMIT): ")?;
+    if location > 0.0 {
+        let width = prompt_float("ENTER BAND WIDTH (CALIBERS): ")?;
+        Ok(Some(BandPosition { location, width }))
+    } else {
+        Ok(None)
+    }
+}
+
+fn run_model(input: &ProjectileInput) -> io::Result<()> {
+    print!("MODEL FORMAT (STL OR SCAD): ");
+    io::stdout().flush()?;
+    let format = match read_line()?.to_uppercase().as_str() {
+        "SCAD" => ModelFormat::OpenScad,
+        _ => ModelFormat::Stl,
+    };
+    println!();
+
+    let segments = prompt_float("ENTER FACETS AROUND THE AXIS (0 FOR 64): ")? as usize;
+    let nose_segments = prompt_float("ENTER FACETS ALONG THE NOSE (0 FOR 32): ")? as usize;
+    let band = read_band_position(input)?;
+
+    let request = ModelRequest {
+        format,
+        segments,
+        nose_segments,
+        band,
+    };
+    match model::export_model(input, &request) {
+        Ok(text) => write_output(&text)?,
+        Err(e) => println!("MODEL ERROR: {}", e),
+    }
+    Ok(())
+}
+
+fn run_drawing(input: &ProjectileInput) -> io::Result<()> {
+    print!("DRAWING FORMAT (SVG OR DXF): ");
+    io::stdout().flush()?;
+    let format = match read_line()?.to_uppercase().as_str() {
+        "DXF" => DrawingFormat::Dxf,
+        _ => DrawingFormat::Svg,
+    };
+    println!();
+
+    let band = read_band_position(input)?;
+    match drawing::export_profile(input, &DrawingRequest { format, band }) {
+        Ok(text) => write_output(&text)?,
+        Err(e) => println!("DRAWING ERROR: {}", e),
+    }
+    Ok(())
+}
+
+fn run_retardation(input: &ProjectileInput) -> io::Result<()> {
+    let mass = prompt_float("ENTER PROJECTILE MASS (GRAMS): ")?;
+    print!("VELOCITY UNITS (M = M/S, F = FT/S): ");
+    io::stdout().flush()?;
+    let unit = match read_line()?.to_uppercase().as_str() {
+        "F" => VelocityUnit::FeetPerSecond,
+        _ => VelocityUnit::MetersPerSecond,
+    };
+    println!();
+    let velocity_min = prompt_float("ENTER LOWEST VELOCITY: ")?;
+    let velocity_max = prompt_float("ENTER HIGHEST VELOCITY: ")?;
+    let velocity_step = prompt_float("ENTER VELOCITY STEP: ")?;
+    let temperature = prompt_float("ENTER AIR TEMPERATURE (DEG C): ")?;
+    let pressure = prompt_float("ENTER STATION PRESSURE (HPA): ")?;
+
+    let params = RetardationInput {
+        mass,
+        velocity_min,
+        velocity_max,
+        velocity_step,
+        unit,
+        atmosphere: Atmosphere {
+            temperature,
+            pressure,
+            humidity: 0.0,
+        },
+    };
+    match retardation::retardation_table(input, &params) {
+        Ok(table) => print!("{}", table.to_text()),
+        Err(e) => println!("RETARDATION TABLE ERROR: {}", e),
+    }
+    Ok(())
+}
+
+fn run_yaw_history(input: &ProjectileInput, params: &TrajectoryInput) -> io::Result<()> {
+    let first_max_yaw = prompt_float("ENTER FIRST MAXIMUM YAW AT MUZZLE EXIT (DEGREES, 0 TO SKIP): ")?;
+    if first_max_yaw <= 0.0 {
+        return Ok(());
+    }
+    let distance = prompt_float("ENTER AVERAGING DISTANCE (M): ")?;
+
+    match input.yaw_history(params, first_max_yaw, distance) {
+        Ok(history) => {
+            println!("TRICYCLIC YAW AT MACH {:5.3}", history.mach);
+            println!();
+            println!("  ARM     FREQUENCY   DAMPING");
+            println!("          (RAD/CAL)  (1/CAL)");
+            println!("  FAST  {:10.5} {:10.6}", history.fast_frequency, history.fast_damping);
+            println!("  SLOW  {:10.5} {:10.6}", history.slow_frequency, history.slow_damping);
+            println!();
+            println!("MEAN SQUARED YAW OVER {:.0} M: {:.6}", history.distance, history.mean_squared_yaw);
+            println!("CD0 {:6.4}   AVERAGE CD WITH YAW {:6.4}   INCREASE {:5.2} PERCENT",
+                     history.cd0, history.average_cd,
+                     100.0 * (history.average_cd / history.cd0 - 1.0));
+        }
+        Err(e) => println!("YAW HISTORY ERROR: {}", e),
+    }
+    Ok(())
+}
+
+/// Outputs beyond the 1974 drag table, grouped behind one prompt so that the
+/// legacy dialogue only gains that prompt.
+fn run_extra_outputs(input: &ProjectileInput, results: &[DragCoefficients]) -> io::Result<()> {
+    if ask_yes_no("DRAW THE PROFILE? (ENTER Y FOR YES, N FOR NO): ")? {
         println!();
+        run_drawing(input)?;
+        println!();
+    }
 
-        print!("ENTER ROTATING BAND DIAMETER (CALIBERS): ");
-        io::stdout().flush()?;
-        let band_diameter = read_float()?;
+    println!();
+    if ask_yes_no("EXPORT A 3D MODEL? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        run_model(input)?;
         println!();
+    }
 
-        println!("[NOTE: CENTER OF GRAVITY LOCATION IS OPTIONAL; IF UNKNOWN, ENTER 0]");
+    println!();
+    if ask_yes_no("PRINT VALIDITY ENVELOPE? (ENTER Y FOR YES, N FOR NO): ")? {
         println!();
-        print!("ENTER CENTER OF GRAVITY LOCATION (CALIBERS FROM NOSE): ");
-        io::stdout().flush()?;
-        let cg_location = read_float()?;
+        print_envelope(input, results);
         println!();
+    }
 
-        println!("FOR ALL LAMINAR BOUNDARY LAYER, CODE = L/L");
-        println!("FOR LAMINAR NOSE, TURBULENT AFTERBODY, CODE = L/T");
-        println!("FOR ALL TURBULENT BOUNDARY LAYER, CODE = T/T");
+    if input.is_flare() {
         println!();
+        if ask_yes_no("PRINT FLARE DRAG TABLE? (ENTER Y FOR YES, N FOR NO): ")? {
+            println!();
+            print_flare_drag(input);
+            println!();
+        }
+    }
 
-        let boundary_layer = loop {
-            print!("ENTER THE BOUNDARY LAYER CODE (L/L, L/T, OR T/T): ");
-            io::stdout().flush()?;
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            let input = input.trim();
-            
-            if let Some(bl) = BoundaryLayer::from_str(input) {
-                break bl;
-            } else {
-                println!("INCORRECT BOUNDARY LAYER CODE. PLEASE TRY AGAIN.");
-            }
-        };
+    println!();
+    if ask_yes_no("PRINT YAW DRAG TABLE? (ENTER Y FOR YES, N FOR NO): ")? {
         println!();
+        print_yaw_drag(input, results);
+        println!();
+    }
 
-        print!("ENTER PROJECTILE IDENTIFICATION: ");
-        io::stdout().flush()?;
-        let mut identification = String::new();
-        io::stdin().read_line(&mut identification)?;
-        let identification = identification.trim().to_string();
-
-        Ok(ProjectileInput {
-            ref_diameter,
-            total_length,
-            nose_length,
-            rt_r,
-            boattail_length,
-            base_diameter,
-            meplat_diameter,
-            band_diameter,
-            cg_location,
-            boundary_layer,
-            identification,
-        })
+    println!();
+    if ask_yes_no("PRINT VELOCITY DECAY TABLE? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        run_retardation(input)?;
+        println!();
     }
 
-    fn calculate_drag_coefficients(&self) -> Vec<DragCoefficients> {
-        let mach_numbers = vec![
-            0.5, 0.6, 0.7, 0.8, 0.85, 0.9, 0.925, 0.95, 0.975, 1.0,
-            1.1, 1.2, 1.3, 1.4, 1.5, 1.6, 1.7, 1.8, 2.0, 2.2,
-            2.5, 3.0, 3.5, 4.0, 4.5, 5.0,
-        ];
-
-        let mut results = Vec::new();
-
-        for &mach in &mach_numbers {
-            let t1 = (1.0 - self.meplat_diameter) / self.nose_length;
-            let m2 = mach * mach;
-            let reynolds = 23296.3 * mach * self.total_length * self.ref_diameter;
-            let log_reynolds = reynolds.ln() * 0.4343;
-            
-            // Skin friction coefficients
-            let c7 = (1.328 / reynolds.sqrt()) * (1.0 + 0.12 * m2).powf(-0.12);
-            let c8 = (0.455 / log_reynolds.powf(2.58)) * (1.0 + 0.21 * m2).powf(-0.32);
-            
-            // Surface area calculations
-            let d5 = 1.0 + (0.333 + 0.02 / (self.nose_length * self.nose_length)) * self.rt_r;
-            let s1 = 1.5708 * self.nose_length * d5 * (1.0 + 1.0 / (8.0 * self.nose_length * self.nose_length));
-            let s2 = 3.1416 * (self.total_length - self.nose_length);
-            let s3 = s1 + s2;
-            
-            // Boundary layer dependent skin friction
-            let (c9, c10) = match self.boundary_layer {
-                BoundaryLayer::LaminarLaminar => {
-                    let c = 1.2732 * s3 * c7;
-                    (c, c)
-                }
-                BoundaryLayer::LaminarTurbulent => {
-                    (1.2732 * s3 * c7, 1.2732 * s3 * c8)
-                }
-                BoundaryLayer::TurbulentTurbulent => {
-                    let c = 1.2732 * s3 * c8;
-                    (c, c)
+    println!();
+    if ask_yes_no("RUN A TRAJECTORY? (ENTER Y FOR YES, N FOR NO): ")? {
+        println!();
+        let params = read_trajectory_input()?;
+        clear_screen();
+        match trajectory::range_card(input, &params) {
+            Ok(card) => {
+                print_range_card(&card);
+                println!();
+                run_yaw_history(input, &params)?;
+                println!();
+                run_firing_tables(input, &params)?;
+                println!();
+                if ask_yes_no("TRUE THE DRAG CURVE FROM OBSERVATIONS? (ENTER Y FOR YES, N FOR NO): ")? {
+                    println!();
+                    run_truing(input, &params)?;
                 }
-            };
-            
-            let cdsf = (c9 * s1 + c10 * s2) / s3;
-            
-            // Wave drag calculations
-            let c15 = (m2 - 1.0) / (2.4 * m2);
-            
-            let p5 = if mach <= 1.0 {
-                (1.0 + 0.2 * m2).powf(3.5)
-            } else {
-                (1.2 * m2).powf(3.5) * (6.0 / (7.0 * m2 - 1.0)).powf(2.5)
-            };
-            
-            let c16 = (1.122 * (p5 - 1.0) * self.meplat_diameter * self.meplat_diameter) / m2;
-            
-            let c18 = if mach <= 0.91 {
-                0.0
-            } else if mach >= 1.41 {
-                0.85 * c16
-            } else {
-                (0.254 + 2.88 * c15) * c16
-            };
-            
-            // Base pressure calculations
-            let p2 = if mach < 1.0 {
-                1.0 / (1.0 + 0.1875 * m2 + 0.0531 * m2 * m2)
-            } else {
-                1.0 / (1.0 + 0.2477 * m2 + 0.0345 * m2 * m2)
-            };
-            
-            let p4 = (1.0 + 0.09 * m2 * (1.0 - (-self.total_length + self.nose_length).exp())) 
-                     * (1.0 + 0.25 * m2 * (1.0 - self.base_diameter));
-            
-            let pb_pinf = (p2 * p4).max(0.0);
-            
-            let cdb = (1.4286 * (1.0 - pb_pinf) * self.base_diameter * self.base_diameter) / m2;
-            
-            // Band drag
-            let cdbnd = if mach < 0.95 {
-                mach.powf(12.5) * (self.band_diameter - 1.0)
-            } else {
-                (0.21 + 0.28 / m2) * (self.band_diameter - 1.0)
-            };
-            
-            // Calculate head and boattail drag based on Mach regime
-            let (cdh, cdbt) = if mach <= 1.0 {
-                // Subsonic/transonic
-                let x2 = (1.0 + 0.552 * t1.powf(0.8)).powf(-0.5);
-                let c17 = if mach <= x2 {
-                    0.0
-                } else {
-                    0.368 * t1.powf(1.8) + 1.6 * t1 * c15
-                };
-                
-                let cdbt = if self.boattail_length <= 0.0 || mach <= 0.85 {
-                    0.0
-                } else {
-                    let t2 = (1.0 - self.base_diameter) / (2.0 * self.boattail_length);
-                    let t3 = 2.0 * t2 * t2 + t2 * t2 * t2;
-                    let e1 = (-2.0 * self.boattail_length).exp();
-                    let b4 = 1.0 - e1 + 2.0 * t2 * (e1 * (self.boattail_length + 0.5) - 0.5);
-                    2.0 * t3 * b4 * (1.0 / (0.564 + 1250.0 * c15 * c15))
-                };
-                
-                (c17 + c18, cdbt)
-            } else {
-                // Supersonic
-                let b2 = m2 - 1.0;
-                let b = b2.sqrt();
-                
-                let s4 = 1.0 + 0.368 * t1.powf(1.85);
-                let z = if mach >= s4 { b } else { (s4 * s4 - 1.0).sqrt() };
-                
-                let c11 = 0.7156 - 0.5313 * self.rt_r + 0.595 * self.rt_r * self.rt_r;
-                let c12 = 0.0796 + 0.0779 * self.rt_r;
-                let c13 = 1.587 + 0.049 * self.rt_r;
-                let c14 = 0.1122 + 0.1658 * self.rt_r;
-                
-                let r4 = 1.0 / (z * z);
-                let c17 = (c11 - c12 * t1 * t1) * r4 * (t1 * z).powf(c13 + c14 * t1);
-                
-                let cdbt = if self.boattail_length <= 0.0 {
-                    0.0
-                } else {
-                    let t2 = (1.0 - self.base_diameter) / (2.0 * self.boattail_length);
-                    
-                    if mach <= 1.1 {
-                        let t3 = 2.0 * t2 * t2 + t2 * t2 * t2;
-                        let e1 = (-2.0 * self.boattail_length).exp();
-                        let b4 = 1.0 - e1 + 2.0 * t2 * (e1 * (self.boattail_length + 0.5) - 0.5);
-                        2.0 * t3 * b4 * (1.774 - 9.3 * c15)
-                    } else {
-                        let b3 = 0.85 / b;
-                        let a12 = (5.0 * t1) / (6.0 * b) + (0.5 * t1).powf(2.0) 
-                                  - (0.7435 / m2) * (t1 * mach).powf(1.6);
-                        let a11 = (1.0 - (0.6 * self.rt_r) / mach) * a12;
-                        let e2 = ((-1.1952 / mach) * (self.total_length - self.nose_length - self.boattail_length)).exp();
-                        let x3 = ((2.4 * m2 * m2 - 4.0 * b2) * t2 * t2) / (2.0 * b2 * b2);
-                        let a1 = a11 * e2 - x3 + (2.0 * t2) / b;
-                        let r5 = 1.0 / b3;
-                        let e3 = (-b3 * self.boattail_length).exp();
-                        let a2 = 1.0 - e3 + 2.0 * t2 * (e3 * (self.boattail_length + r5) - r5);
-                        4.0 * a1 * t2 * a2 * r5
-                    }
-                };
-                
-                (c17 + c18, cdbt)
-            };
-            
-            let cd0 = cdh + cdsf + cdbnd + cdbt + cdb;
-            
-            results.push(DragCoefficients {
-                mach,
-                cd0,
-                cdh,
-                cdsf,
-                cdbnd,
-                cdbt,
-                cdb,
-                pb_pinf,
-            });
+            }
+            Err(e) => println!("TRAJECTORY ERROR: {}", e),
         }
-        
-        results
+        println!();
     }
+    Ok(())
+}
 
-    fn print_diagnostics(&self) {
-        if self.nose_length < 1.0 {
-            println!("NOSE TOO SHORT. CDH IS TOO HIGH AT TRANSONIC AND SUPERSONIC SPEEDS.");
-        }
-        if self.meplat_diameter > 0.5 {
-            println!("NOSE TOO BLUNT. CDH IS TOO HIGH AT TRANSONIC AND SUPERSONIC SPEEDS.");
-        }
-        if self.boattail_length >= 1.5 {
-            println!("BOATTAIL TOO LONG. CDBT AND CDB MAY BE INCORRECT.");
-        }
-        if self.base_diameter < 0.65 {
-            println!("BOATTAIL TOO STEEP. CDBT AND CDB MAY BE INCORRECT.");
-        } else if self.base_diameter > 1.35 {
-            println!("CONICAL FLARE TAIL TOO STEEP. CDBT AND CDB MAY BE INCORRECT.");
-        }
+fn print_range_card(card: &RangeCard) {
+    println!("MODIFIED POINT-MASS RANGE CARD");
+    println!();
+    let stability = if card.stability_factor.is_finite() {
+        format!("{:6.2}", card.stability_factor)
+    } else {
+        "   N/A".to_string()
+    };
+    println!("GYROSCOPIC STABILITY FACTOR: {}   MUZZLE SPIN: {:7.0} REV/S   DEPARTURE ANGLE: {:7.4} DEG",
+             stability, card.muzzle_spin_rate, card.departure_angle);
+    println!();
+    println!(" RANGE    VEL   MACH   TIME    DROP   ELEV   ELEV   DRIFT   WIND   WIND    SPIN   REPOSE");
+    println!("  (M)   (M/S)          (S)     (CM)  (MOA)  (MIL)    (CM)  (MOA)  (MIL)    (CM)   (DEG)");
+    println!();
+
+    for row in &card.rows {
+        println!("{:6.0} {:6.1} {:5.3} {:6.3} {:7.1} {:6.2} {:6.2} {:7.1} {:6.2} {:6.2} {:7.1} {:7.4}",
+                 row.range, row.velocity, row.mach, row.time, row.drop * 100.0,
+                 row.elevation_moa, row.elevation_mil, row.windage * 100.0,
+                 row.windage_moa, row.windage_mil, row.spin_drift * 100.0, row.yaw_of_repose);
     }
 }
 
@@ -341,10 +992,19 @@ fn clear_screen() {
 }
 
 fn main() -> io::Result<()> {
+    // A measured profile named on the command line supplies the first case.
+    let mut imported = match std::env::args().nth(1) {
+        Some(path) => import_case(&path)?,
+        None => None,
+    };
+
     loop {
         clear_screen();
         
-        let input = ProjectileInput::new()?;
+        let (input, fit_report) = match imported.take() {
+            Some((input, report)) => (input, Some(report)),
+            None => (read_projectile_input()?, None),
+        };
         
         clear_screen();
         println!("MCDRAG, DECEMBER 1974, R. L. MCCOY");
@@ -364,6 +1024,41 @@ fn main() -> io::Result<()> {
                  input.boattail_length, input.base_diameter, input.meplat_diameter,
                  input.band_diameter, input.cg_location, input.boundary_layer.to_str());
         println!();
+        let ogive = input.ogive_geometry();
+        match ogive.ogive_radius {
+            _ if input.is_sphere() => println!("SPHERE, REYNOLDS NUMBER {:.0} AT MACH 1", input.sphere_reynolds(1.0)),
+            Some(radius) => println!("OGIVE RADIUS {:.3} CAL ({} TANGENT RADIUS {:.3} CAL), JUNCTION ANGLE {:.2} DEG",
+                                     radius, if input.rt_r < 1.0 { "SECANT," } else { "TANGENT," },
+                                     ogive.tangent_radius, ogive.junction_angle),
+            None => println!("CONICAL NOSE, JUNCTION ANGLE {:.2} DEG", ogive.junction_angle),
+        }
+        if input.boattail_length > 0.0 && !input.is_flare() && !input.is_sphere() {
+            match input.boattail_shape {
+                Some(shape) => println!("EFFECTIVE BOATTAIL ANGLE {:.2} DEG (NOMINAL {:.2} DEG), TRANSITION RADIUS {:.3} CAL, REBATE {:.3} CAL",
+                                        input.effective_boattail_angle(), input.boattail_angle(),
+                                        shape.transition_radius, shape.rebate_depth),
+                None => println!("BOATTAIL ANGLE {:.2} DEG", input.boattail_angle()),
+            }
+        }
+        if input.is_flare() {
+            println!("FLARE AFTERBODY: HALF-ANGLE {:.2} DEG, BASE {:.3} CAL, WETTED AREA {:.3} SQ CAL",
+                     -input.boattail_angle(), input.base_diameter, input.flare_wetted_area());
+        }
+        if let Some(cap) = input.tip_cap() {
+            println!("{} TIP RADIUS {:.3} CAL, CAP HALF-ANGLE {:.1} DEG, MEPLAT FACE DRAG X {:.3}",
+                     if matches!(input.nose_tip, Some(NoseTip::Polymer)) { "POLYMER" } else { "ROUNDED" },
+                     cap.radius, cap.half_angle, cap.face_factor);
+        }
+        if let Some(cavity) = input.tip_cavity {
+            println!("TIP CAVITY DIA {:.3} CAL, DEPTH {:.3} CAL, {:.0}% OF MEPLAT, MEPLAT FACE DRAG X {:.3}",
+                     cavity.diameter, cavity.depth, 100.0 * input.cavity_area_fraction(),
+                     input.meplat_face_factor());
+        }
+        if input.base_shape.is_some() {
+            println!("BASE DRAG RELIEF {:.1}% AT MACH 0.8, {:.1}% AT MACH 2, EFFECTIVE BASE DIA {:.3} CAL",
+                     100.0 * input.base_cavity_relief(0.8), 100.0 * input.base_cavity_relief(2.0),
+                     input.effective_base_diameter());
+        }
         println!();
         
         // Calculate and print results
@@ -381,8 +1076,41 @@ fn main() -> io::Result<()> {
         println!();
         println!();
         
+        if input.compound_nose.is_some() {
+            print_head_drag_split(&input);
+            println!();
+            println!();
+        }
+        if !input.bands.is_empty() {
+            print_band_drag(&input);
+            println!();
+            println!();
+        }
+        if input.base_bleed.is_some() || input.rocket_motor.is_some() {
+            print_power_off_drag(&input, &results);
+            println!();
+            println!();
+        }
+        if input.surface.is_some() {
+            print_surface_drag(&results);
+            println!();
+            println!();
+        }
+        if input.fins.is_some() {
+            print_fin_drag(&input, &results);
+            println!();
+            println!();
+        }
+        
         // Print diagnostics
-        input.print_diagnostics();
+        for diagnostic in input.get_diagnostics() {
+            println!("{}", diagnostic);
+        }
+
+        if let Some(report) = fit_report {
+            println!();
+            print!("{}", report);
+        }
         
         println!();
         println!();
@@ -397,6 +1125,12 @@ fn main() -> io::Result<()> {
             println!("[Note: Hardcopy printing not implemented in this version]");
         }
         
+        println!();
+        if ask_yes_no("EXTRA OUTPUTS? (ENTER Y FOR YES, N FOR NO): ")? {
+            println!();
+            run_extra_outputs(&input, &results)?;
+        }
+
         println!();
         print!("RUN ANOTHER CASE? ENTER Y FOR YES, N FOR NO: ");
         io::stdout().flush()?;
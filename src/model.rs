@@ -0,0 +1,327 @@
+use serde::{Deserialize, Serialize};
+
+use crate::drawing::BandPosition;
+use crate::ProjectileInput;
+
+/// Tessellation used when a request leaves it at 0.
+const DEFAULT_SEGMENTS: usize = 64;
+const DEFAULT_NOSE_SEGMENTS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ModelFormat {
+    Stl,
+    OpenScad,
+}
+
+/// 3D model export. `segments` is the number of facets around the axis and
+/// `nose_segments` the number of facets along each nose segment; 0 selects
+/// the defaults (64 and 32).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRequest {
+    pub format: ModelFormat,
+    #[serde(default)]
+    pub segments: usize,
+    #[serde(default)]
+    pub nose_segments: usize,
+    #[serde(default)]
+    pub band: Option<BandPosition>,
+}
+
+type Point = (f64, f64, f64);
+
+impl ProjectileInput {
+    /// Closed half-profile (x, r) in calibers from the tip on the axis,
+    /// around the body and back to the axis at the base; for a sphere, the
+    /// half circle.
+    pub fn profile_points(&self, band: Option<&BandPosition>, nose_segments: usize) -> Result<Vec<(f64, f64)>, String> {
+        self.check_profile(band)?;
+        let nose_segments = nose_segments.max(1);
+        if self.is_sphere() {
+            // A circle of one caliber, with as many facets on each half as
+            // a nose segment.
+            let steps = 2 * nose_segments;
+            return Ok((0..=steps)
+                .map(|i| {
+                    let angle = std::f64::consts::PI * i as f64 / steps as f64;
+                    (0.5 - 0.5 * angle.cos(), if i == steps { 0.0 } else { 0.5 * angle.sin() })
+                })
+                .collect());
+        }
+
+        let contour = self.contour(band);
+        let mut points = vec![contour[0].start()];
+        for piece in &contour {
+            points.extend(piece.points(nose_segments));
+        }
+        // Arc ends on the axis land there exactly, so the mesh closes.
+        for point in &mut points {
+            if point.1.abs() < 1e-9 {
+                point.1 = 0.0;
+            }
+        }
+
+        points.dedup_by(|a, b| (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9);
+        Ok(points)
+    }
+
+    /// ASCII STL of the body of revolution in mm, axis along +x with the
+    /// meplat at the origin; a rounded tip stands ahead of it. Facets at
+    /// the tip and base centres are triangle fans on shared axis vertices,
+    /// so the mesh is closed. Fins are separate closed plates overlapping
+    /// the body.
+    pub fn to_stl(&self, band: Option<&BandPosition>, segments: usize, nose_segments: usize) -> Result<String, String> {
+        let profile = self.profile_points(band, tessellation(nose_segments, DEFAULT_NOSE_SEGMENTS))?;
+        let segments = tessellation(segments, DEFAULT_SEGMENTS).max(3);
+        let d = self.ref_diameter;
+        let vertex = |(x, r): (f64, f64), j: usize| -> Point {
+            let angle = 2.0 * std::f64::consts::PI * (j % segments) as f64 / segments as f64;
+            (x * d, r * d * angle.cos(), r * d * angle.sin())
+        };
+
+        let name = self.identification.split_whitespace().collect::<Vec<_>>().join("_");
+        let mut out = format!("solid {}\n", name);
+        for pair in profile.windows(2) {
+            let (p0, p1) = (pair[0], pair[1]);
+            for j in 0..segments {
+                let a = vertex(p0, j);
+                let b = vertex(p0, j + 1);
+                let c = vertex(p1, j + 1);
+                let e = vertex(p1, j);
+                // Wound counterclockwise seen from outside; a triangle
+                // collapses where the profile touches the axis.
+                if p0.1 > 0.0 {
+                    write_facet(&mut out, a, b, c);
+                }
+                if p1.1 > 0.0 {
+                    write_facet(&mut out, a, c, e);
+                }
+            }
+        }
+        if let (Some(fins), Some(mut planform)) = (self.fins, self.fin_planform()) {
+            // Each fin is a closed plate of its thickness, its root sunk
+            // into the body so the plate corners do not stand off it.
+            let half = 0.5 * fins.thickness;
+            planform[0].1 -= half;
+            planform[1].1 -= half;
+            for k in 0..fins.count {
+                let angle = 2.0 * std::f64::consts::PI * k as f64 / fins.count as f64;
+                let (sin, cos) = angle.sin_cos();
+                let vertex = |(x, r): (f64, f64), side: f64| -> Point {
+                    (x * d, (r * cos - side * half * sin) * d, (r * sin + side * half * cos) * d)
+                };
+                let upper = planform.map(|point| vertex(point, 1.0));
+                let lower = planform.map(|point| vertex(point, -1.0));
+                // Faces wound counterclockwise seen from outside the plate.
+                write_facet(&mut out, upper[0], upper[1], upper[2]);
+                write_facet(&mut out, upper[0], upper[2], upper[3]);
+                write_facet(&mut out, lower[0], lower[2], lower[1]);
+                write_facet(&mut out, lower[0], lower[3], lower[2]);
+                for i in 0..4 {
+                    let j = (i + 1) % 4;
+                    write_facet(&mut out, lower[i], lower[j], upper[j]);
+                    write_facet(&mut out, lower[i], upper[j], upper[i]);
+                }
+            }
+        }
+        out.push_str(&format!("endsolid {}\n", name));
+        Ok(out)
+    }
+
+    /// Parametric OpenSCAD script for the same body. The MCDRAG inputs are
+    /// variables at the top and the ogive is rebuilt in OpenSCAD, so the
+    /// shape can be edited there.
+    pub fn to_openscad(&self, band: Option<&BandPosition>, segments: usize, nose_segments: usize) -> Result<String, String> {
+        self.check_profile(band)?;
+        if self.is_sphere() {
+            let mut out = String::new();
+            out.push_str(&format!("// {}\n", self.identification));
+            out.push_str("// Generated by MCDRAG. Sphere along +x from the origin, model in mm.\n\n");
+            out.push_str(&format!("{:<23} // {}\n", format!("d = {};", self.ref_diameter), "diameter, mm"));
+            out.push_str(&format!(
+                "{:<23} // {}\n\n",
+                format!("$fn = {};", tessellation(segments, DEFAULT_SEGMENTS)),
+                "facets around the axis"
+            ));
+            out.push_str("translate([d / 2, 0, 0]) sphere(d = d);\n");
+            return Ok(out);
+        }
+        let bands = self
+            .cylinder_rings(band)
+            .iter()
+            .map(|(start, width, diameter)| format!("[{}, {}, {}]", start, width, diameter))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let (tip_length, tip_dia, tip_rt_r) =
+            self.compound_nose.map_or((0.0, 0.0, 0.0), |tip| (tip.tip_length, tip.tip_diameter, tip.tip_rt_r));
+
+        let shape = self.boattail_shape.unwrap_or_default();
+        let (tip_radius, tip_angle) = self
+            .tip_cap()
+            .filter(|cap| self.meplat_diameter > 0.0 && cap.height > 0.0)
+            .map_or((0.0, 0.0), |cap| (cap.radius, cap.half_angle));
+        let (cavity_dia, cavity_depth) = self.tip_cavity.map_or((0.0, 0.0), |cavity| (cavity.diameter, cavity.depth));
+        let fins = self.fins.map_or([0.0; 5], |fins| {
+            [fins.count as f64, fins.root_chord, fins.tip_chord, fins.span, fins.thickness]
+        });
+        let fin_base = self.fin_planform().map_or(0.0, |planform| planform[0].1);
+        let base_shape = self.base_shape.unwrap_or_default();
+        let (base_cavity_dia, base_cavity_depth) = base_shape.drawn_cavity(self.base_diameter).unwrap_or((0.0, 0.0));
+
+        let mut out = String::new();
+        out.push_str(&format!("// {}\n", self.identification));
+        out.push_str("// Generated by MCDRAG. Lengths in calibers unless noted; axis along +x,\n");
+        out.push_str("// meplat at the origin, model in mm.\n\n");
+        let variables = [
+            ("d", self.ref_diameter, "reference diameter, mm"),
+            ("length", self.total_length, "total length"),
+            ("nose", self.nose_length, "nose length"),
+            ("rt_r", self.rt_r, "headshape parameter RT/R (0 = cone)"),
+            ("boattail", self.boattail_length, "boattail length"),
+            ("rebate", shape.rebate_depth, "boattail rebate step depth (0 = none)"),
+            ("transition_radius", shape.transition_radius, "boattail transition arc radius (0 = none)"),
+            ("boattail_angle", self.effective_boattail_angle(), "half-angle of the straight boattail, deg"),
+            ("base", self.base_diameter, "base diameter"),
+            ("edge_radius", base_shape.edge_radius, "base edge radius (0 = square)"),
+            ("base_cavity_dia", base_cavity_dia, "base cup, hollow or tracer cavity diameter (0 = none)"),
+            ("base_cavity_depth", base_cavity_depth, "base cavity depth"),
+            ("meplat", self.meplat_diameter, "meplat diameter"),
+            ("tip_radius", tip_radius, "rounded tip radius (0 = flat meplat)"),
+            ("tip_angle", tip_angle, "rounded tip cap half-angle, deg"),
+            ("cavity_dia", cavity_dia, "tip cavity diameter (0 = none)"),
+            ("cavity_depth", cavity_depth, "tip cavity depth"),
+            ("tip_length", tip_length, "compound nose tip segment length (0 = none)"),
+            ("tip_dia", tip_dia, "tip segment base diameter"),
+            ("tip_rt_r", tip_rt_r, "tip segment RT/R (0 = cone)"),
+            ("band", self.band_diameter, "rotating band diameter"),
+            ("fin_count", fins[0], "number of fins (0 = none)"),
+            ("fin_root", fins[1], "fin root chord, trailing edge at the base"),
+            ("fin_tip", fins[2], "fin tip chord"),
+            ("fin_span", fins[3], "fin span from the body"),
+            ("fin_thickness", fins[4], "fin thickness"),
+            ("fin_base", fin_base, "body radius under the fin root"),
+            ("nose_steps", tessellation(nose_segments, DEFAULT_NOSE_SEGMENTS) as f64, "facets along the nose"),
+            ("$fn", tessellation(segments, DEFAULT_SEGMENTS) as f64, "facets around the axis"),
+        ];
+        for (name, value, comment) in variables {
+            out.push_str(&format!("{:<23} // {}\n", format!("{} = {};", name, value), comment));
+        }
+        out.push_str(&format!(
+            "{:<23} // bands and grooves drawn: start from the nose tip, width, diameter\n",
+            format!("bands = [{}];", bands)
+        ));
+        out.push('\n');
+        out.push_str(OPENSCAD_BODY);
+        Ok(out)
+    }
+}
+
+/// OpenSCAD geometry shared by every script. Each nose segment is an arc
+/// from its start to its end with radius tangent radius / RT/R, or a
+/// straight line for RT/R = 0: [x0, r0, x1, r1, xc, yc, R]. The boattail
+/// angle is solved in MCDRAG, so a changed transition radius needs a new
+/// export.
+const OPENSCAD_BODY: &str = "function segment(x0, r0, x1, r1, rt) =
+    let (len = x1 - x0, rise = r1 - r0, chord = sqrt(len * len + rise * rise),
+         R = rt > 0 ? max((len * len + rise * rise) / (2 * rise) / rt, chord / 2) : 0,
+         offset = R > 0 ? sqrt(R * R - chord * chord / 4) : 0)
+    [x0, r0, x1, r1, x0 + len / 2 + offset * rise / chord, (r0 + r1) / 2 - offset * len / chord, R];
+
+function segment_radius(s, x) = s[6] > 0
+    ? s[5] + sqrt(max(s[6] * s[6] - (x - s[4]) * (x - s[4]), 0))
+    : s[1] + (s[3] - s[1]) * (x - s[0]) / (s[2] - s[0]);
+
+tip = meplat / 2;
+
+// A rounded tip is a spherical cap over the meplat, standing ahead of it;
+// a tip cavity opens through its middle.
+cavity_angle = tip_radius > 0 ? asin(cavity_dia / 2 / tip_radius) : 0;
+front = concat(
+    cavity_dia > 0 ? [[0, cavity_depth], [cavity_dia / 2, cavity_depth]] : tip_radius > 0 ? [] : [[0, 0]],
+    tip_radius > 0
+        ? [for (i = [0 : nose_steps]) let (a = cavity_angle + (tip_angle - cavity_angle) * i / nose_steps)
+            [tip_radius * sin(a), tip_radius * (cos(tip_angle) - cos(a))]]
+        : concat(cavity_dia > 0 ? [[cavity_dia / 2, 0]] : [], meplat > 0 ? [[tip, 0]] : []));
+nose_segments = tip_length > 0
+    ? [segment(0, tip, tip_length, tip_dia / 2, tip_rt_r), segment(tip_length, tip_dia / 2, nose, 0.5, rt_r)]
+    : [segment(0, tip, nose, 0.5, rt_r)];
+
+// The boattail steps down by the rebate, follows the transition arc until
+// it meets the straight part at boattail_angle, then runs to the base.
+shoulder = 0.5 - rebate;
+arc_end = transition_radius * sin(boattail_angle);
+boattail_profile = boattail > 0
+    ? concat(
+        [[0.5, length - boattail]],
+        rebate > 0 ? [[shoulder, length - boattail]] : [],
+        transition_radius > 0
+            ? [for (i = [1 : nose_steps]) let (x = arc_end * i / nose_steps)
+                [shoulder - transition_radius + sqrt(transition_radius * transition_radius - x * x), length - boattail + x]]
+            : [])
+    : [];
+
+// A radiused base edge is tangent to the side, which meets the base at
+// boattail_angle, and to the base face; the face steps into any cavity.
+base_r = boattail > 0 ? base / 2 : 0.5;
+edge_center = [base_r + edge_radius * ((1 - sin(boattail_angle)) * tan(boattail_angle) - cos(boattail_angle)),
+    length - edge_radius];
+base_profile = concat(
+    edge_radius > 0
+        ? [for (i = [0 : nose_steps]) let (a = (90 - boattail_angle) * (1 - i / nose_steps))
+            [edge_center[0] + edge_radius * sin(a), edge_center[1] + edge_radius * cos(a)]]
+        : [[base_r, length]],
+    base_cavity_dia > 0
+        ? [[base_cavity_dia / 2, length], [base_cavity_dia / 2, length - base_cavity_depth],
+            [0, length - base_cavity_depth]]
+        : [[0, length]]);
+
+profile = concat(
+    front,
+    [for (s = nose_segments) for (i = [1 : nose_steps])
+        let (x = s[0] + (s[2] - s[0]) * i / nose_steps) [i == nose_steps ? s[3] : segment_radius(s, x), x]],
+    [for (b = bands) each [[0.5, b[0]], [b[2] / 2, b[0]], [b[2] / 2, b[0] + b[1]], [0.5, b[0] + b[1]]]],
+    boattail_profile,
+    base_profile
+);
+
+scale(d) rotate([0, 90, 0]) rotate_extrude() polygon(profile);
+
+// Each fin is a plate of its thickness in a plane through the axis, its
+// root sunk into the body so the plate corners do not stand off it.
+if (fin_count > 0)
+    scale(d) for (k = [0 : fin_count - 1]) rotate([360 * k / fin_count, 0, 0])
+        linear_extrude(height = fin_thickness, center = true)
+            polygon([[length - fin_root, fin_base - fin_thickness / 2], [length, fin_base - fin_thickness / 2],
+                [length, fin_base + fin_span], [length - fin_tip, fin_base + fin_span]]);
+";
+
+/// Requested facet count, or `default` when it was left at 0.
+fn tessellation(requested: usize, default: usize) -> usize {
+    if requested == 0 {
+        default
+    } else {
+        requested
+    }
+}
+
+fn write_facet(out: &mut String, a: Point, b: Point, c: Point) {
+    let u = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+    let v = (c.0 - a.0, c.1 - a.1, c.2 - a.2);
+    let n = (u.1 * v.2 - u.2 * v.1, u.2 * v.0 - u.0 * v.2, u.0 * v.1 - u.1 * v.0);
+    let length = (n.0 * n.0 + n.1 * n.1 + n.2 * n.2).sqrt();
+    let n = if length > 0.0 { (n.0 / length, n.1 / length, n.2 / length) } else { (0.0, 0.0, 0.0) };
+
+    out.push_str(&format!("  facet normal {:e} {:e} {:e}\n    outer loop\n", n.0, n.1, n.2));
+    for p in [a, b, c] {
+        out.push_str(&format!("      vertex {:e} {:e} {:e}\n", p.0, p.1, p.2));
+    }
+    out.push_str("    endloop\n  endfacet\n");
+}
+
+/// Exports the 3D model in the requested format.
+pub fn export_model(input: &ProjectileInput, request: &ModelRequest) -> Result<String, String> {
+    let band = request.band.as_ref();
+    match request.format {
+        ModelFormat::Stl => input.to_stl(band, request.segments, request.nose_segments),
+        ModelFormat::OpenScad => input.to_openscad(band, request.segments, request.nose_segments),
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectileInput;
+
+/// Height (calibers) of a tip cap below which it cannot be told from a flat
+/// meplat on a comparator.
+const FLAT_CAP_HEIGHT: f64 = 0.005;
+
+/// Rounded tip over the meplat. MCDRAG treats the meplat as a flat face
+/// carrying the full stagnation pressure; a rounded face carries less.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum NoseTip {
+    /// Spherical cap of `radius` calibers spanning the meplat. Half the
+    /// meplat diameter makes it a hemisphere.
+    Radiused { radius: f64 },
+    /// Polymer tip moulded to a round point that meets the nose without a
+    /// corner, so its radius follows from the meplat and the nose slope.
+    Polymer,
+}
+
+/// Cap over the meplat: its radius and height (calibers), half-angle
+/// (degrees), and the fraction of the flat-face meplat drag it carries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TipCap {
+    pub radius: f64,
+    pub half_angle: f64,
+    pub height: f64,
+    pub face_factor: f64,
+}
+
+impl ProjectileInput {
+    /// Geometry of the rounded tip, if any. The face factor is the
+    /// Newtonian pressure drag of a spherical cap of half-angle t over that
+    /// of a flat disk of the same diameter, (1 + cos^2 t) / 2: one half for
+    /// a hemisphere.
+    pub fn tip_cap(&self) -> Option<TipCap> {
+        let half_diameter = 0.5 * self.meplat_diameter;
+        let (radius, half_angle) = match self.nose_tip? {
+            NoseTip::Radiused { radius } => (radius, (half_diameter / radius).clamp(-1.0, 1.0).asin()),
+            NoseTip::Polymer => {
+                let slope = self.nose_segments()[0].slope_at(0.0).atan();
+                (half_diameter / slope.cos(), std::f64::consts::FRAC_PI_2 - slope)
+            }
+        };
+        let cos = half_angle.cos();
+        Some(TipCap {
+            radius,
+            half_angle: half_angle.to_degrees(),
+            height: radius * (1.0 - cos),
+            face_factor: 0.5 * (1.0 + cos * cos),
+        })
+    }
+
+    /// Fraction of McCoy's flat meplat drag the tip carries: the rounded
+    /// tip over the rim and the cavity, if any, over its mouth.
+    pub fn meplat_face_factor(&self) -> f64 {
+        let rim = self.tip_cap().map_or(1.0, |cap| cap.face_factor);
+        let open = self.cavity_area_fraction();
+        let cavity = self.tip_cavity.map_or(1.0, |cavity| cavity.face_factor());
+        (1.0 - open) * rim + open * cavity
+    }
+
+    /// Warns when a radiused tip rises too little above the meplat to be
+    /// measured, so the rounding cannot be told from a flat meplat.
+    pub fn tip_diagnostics(&self) -> Vec<String> {
+        let mut diagnostics = Vec::new();
+        if let (Some(NoseTip::Radiused { radius }), Some(cap)) = (self.nose_tip, self.tip_cap()) {
+            if cap.height < FLAT_CAP_HEIGHT {
+                diagnostics.push(format!(
+                    "TIP RADIUS {:.3} ON A {:.3} MEPLAT RISES {:.4} CAL. NOT DISTINGUISHABLE FROM A FLAT MEPLAT.",
+                    radius, self.meplat_diameter, cap.height
+                ));
+            }
+        }
+        diagnostics
+    }
+}
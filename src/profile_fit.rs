@@ -0,0 +1,624 @@
+use serde::{Deserialize, Serialize};
+
+use crate::drawing::BandPosition;
+use crate::{BoundaryLayer, ProjectileInput, ProjectileType};
+
+/// Stations the measured outline is resampled to before fitting.
+const STATIONS: usize = 2000;
+/// Half-width (stations) of the window used for the outline slope.
+const SLOPE_WINDOW: usize = 5;
+/// Slope below which a station counts as part of a cylinder.
+const FLAT_SLOPE: f64 = 0.01;
+/// Fraction of the body radius within which the outline is on the body.
+const BODY_TOLERANCE: f64 = 0.005;
+/// Shortest boattail (calibers) that is not taken for a rounded base edge.
+const MIN_BOATTAIL: f64 = 0.15;
+const ITERATIONS: usize = 600;
+/// Points per straight DXF segment, and the fewest per arc.
+const LINE_SAMPLES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ProfileFormat {
+    Csv,
+    Dxf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum LengthUnit {
+    #[default]
+    Millimeters,
+    Inches,
+}
+
+impl LengthUnit {
+    pub(crate) fn to_mm(self) -> f64 {
+        match self {
+            LengthUnit::Millimeters => 1.0,
+            LengthUnit::Inches => 25.4,
+        }
+    }
+}
+
+/// A measured profile to import. CSV data has one station per line,
+/// axial position then radius; DXF outlines are read from LINE, ARC,
+/// POLYLINE, LWPOLYLINE and POINT entities. The axis is y = 0 in both;
+/// points on both sides of it are folded together.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileImportRequest {
+    pub format: ProfileFormat,
+    pub data: String,
+    #[serde(default)]
+    pub units: LengthUnit,
+    pub boundary_layer: BoundaryLayer,
+    #[serde(default)]
+    pub identification: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SegmentKind {
+    Meplat,
+    Ogive,
+    Bearing,
+    Band,
+    Boattail,
+}
+
+/// Extent (calibers from the nose) of one part of the profile and how far
+/// the measured outline departs from the fitted one there, in mm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentFit {
+    pub kind: SegmentKind,
+    pub start: f64,
+    pub end: f64,
+    pub rms_residual: f64,
+    pub max_residual: f64,
+}
+
+/// Fitted case. `origin` is the station of the nose tip in the measured
+/// coordinates and `direction` is +1 or -1 as the base lies toward higher or
+/// lower stations.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileFit {
+    pub input: ProjectileInput,
+    pub origin: f64,
+    pub direction: f64,
+    pub band: Option<BandPosition>,
+    pub segments: Vec<SegmentFit>,
+    pub rms_residual: f64,
+    pub max_residual: f64,
+}
+
+/// Reads (x, r) pairs from CSV text. Fields may be separated by commas,
+/// semicolons or white space; lines that do not start with two numbers
+/// (headers, comments) are skipped.
+pub fn parse_csv(text: &str) -> Result<Vec<(f64, f64)>, String> {
+    let points: Vec<(f64, f64)> = text
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line
+                .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+                .filter(|field| !field.is_empty());
+            let x = fields.next()?.parse().ok()?;
+            let r = fields.next()?.parse().ok()?;
+            Some((x, r))
+        })
+        .collect();
+    if points.is_empty() {
+        return Err("No numeric stations found in the CSV data".to_string());
+    }
+    Ok(points)
+}
+
+/// Points along the segment from `a` to `b`, so that steps in the outline
+/// stay steps after resampling.
+fn densify(points: &mut Vec<(f64, f64)>, a: (f64, f64), b: (f64, f64)) {
+    for i in 0..=LINE_SAMPLES {
+        let t = i as f64 / LINE_SAMPLES as f64;
+        points.push((a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1)));
+    }
+}
+
+/// Reads outline points from an ASCII DXF. When an OUTLINE layer exists
+/// only it is used, otherwise dimension, centre line and text layers are
+/// skipped. Arcs and straight segments are sampled densely.
+pub fn parse_dxf(text: &str) -> Result<Vec<(f64, f64)>, String> {
+    let lines: Vec<&str> = text.lines().map(|line| line.trim()).collect();
+    let pairs: Vec<(i32, &str)> = lines
+        .chunks(2)
+        .filter(|pair| pair.len() == 2)
+        .filter_map(|pair| Some((pair[0].parse().ok()?, pair[1])))
+        .collect();
+
+    // Group the ENTITIES section into (type, codes) records.
+    let mut entities: Vec<(&str, Vec<(i32, &str)>)> = Vec::new();
+    let mut in_entities = false;
+    for &(code, value) in &pairs {
+        if code == 2 && value == "ENTITIES" {
+            in_entities = true;
+            continue;
+        }
+        if !in_entities {
+            continue;
+        }
+        if code == 0 {
+            if value == "ENDSEC" {
+                break;
+            }
+            entities.push((value, Vec::new()));
+        } else if let Some((_, codes)) = entities.last_mut() {
+            codes.push((code, value));
+        }
+    }
+
+    let layer = |codes: &[(i32, &str)]| {
+        codes.iter().find(|(code, _)| *code == 8).map_or(String::new(), |(_, v)| v.to_uppercase())
+    };
+    let has_outline = entities.iter().any(|(_, codes)| layer(codes) == "OUTLINE");
+    let wanted = |codes: &[(i32, &str)]| {
+        let name = layer(codes);
+        if has_outline {
+            name == "OUTLINE"
+        } else {
+            !["DIM", "CENTER", "TEXT", "HATCH"].iter().any(|skip| name.contains(skip))
+        }
+    };
+    let number = |codes: &[(i32, &str)], wanted: i32| {
+        codes.iter().find(|(code, _)| *code == wanted).and_then(|(_, v)| v.parse::<f64>().ok())
+    };
+
+    let mut points = Vec::new();
+    let mut polyline: Option<(f64, f64)> = None;
+    let mut in_polyline = false;
+    for (kind, codes) in &entities {
+        match *kind {
+            "POLYLINE" => {
+                in_polyline = wanted(codes);
+                polyline = None;
+            }
+            "SEQEND" => in_polyline = false,
+            "VERTEX" if in_polyline => {
+                if let (Some(x), Some(y)) = (number(codes, 10), number(codes, 20)) {
+                    match polyline {
+                        Some(previous) => densify(&mut points, previous, (x, y)),
+                        None => points.push((x, y)),
+                    }
+                    polyline = Some((x, y));
+                }
+            }
+            _ if !wanted(codes) => {}
+            "LINE" => {
+                if let (Some(x1), Some(y1), Some(x2), Some(y2)) =
+                    (number(codes, 10), number(codes, 20), number(codes, 11), number(codes, 21))
+                {
+                    densify(&mut points, (x1, y1), (x2, y2));
+                }
+            }
+            "POINT" => {
+                if let (Some(x), Some(y)) = (number(codes, 10), number(codes, 20)) {
+                    points.push((x, y));
+                }
+            }
+            "LWPOLYLINE" => {
+                let xs = codes.iter().filter(|(code, _)| *code == 10).filter_map(|(_, v)| v.parse().ok());
+                let ys = codes.iter().filter(|(code, _)| *code == 20).filter_map(|(_, v)| v.parse().ok());
+                let vertices: Vec<(f64, f64)> = xs.zip(ys).collect();
+                for pair in vertices.windows(2) {
+                    densify(&mut points, pair[0], pair[1]);
+                }
+            }
+            "ARC" => {
+                if let (Some(xc), Some(yc), Some(r), Some(start), Some(end)) = (
+                    number(codes, 10),
+                    number(codes, 20),
+                    number(codes, 40),
+                    number(codes, 50),
+                    number(codes, 51),
+                ) {
+                    let sweep = (end - start).rem_euclid(360.0);
+                    let steps = (sweep.ceil() as usize).max(LINE_SAMPLES);
+                    for i in 0..=steps {
+                        let angle = (start + sweep * i as f64 / steps as f64).to_radians();
+                        points.push((xc + r * angle.cos(), yc + r * angle.sin()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    if points.is_empty() {
+        return Err("No outline entities found in the DXF data".to_string());
+    }
+    Ok(points)
+}
+
+/// Outline radius at evenly spaced stations from the nose tip (x = 0) to the
+/// base, in mm, with the measured station of the tip and the direction of
+/// the base from it.
+fn resample(points: &[(f64, f64)]) -> Result<(f64, Vec<f64>, f64, f64), String> {
+    let mut points: Vec<(f64, f64)> = points.iter().map(|&(x, r)| (x, r.abs())).collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let x0 = points[0].0;
+    let length = points[points.len() - 1].0 - x0;
+    if length <= 0.0 || points.len() < 10 {
+        return Err("Profile needs at least ten points spread along the axis".to_string());
+    }
+
+    // Points at one station (a face or a step) are kept as the radius just
+    // before and just after it, whichever is closer to each neighbour.
+    let mut groups: Vec<(f64, f64, f64)> = Vec::new();
+    for (x, r) in points {
+        let x = x - x0;
+        match groups.last_mut() {
+            Some(last) if x - last.0 <= 1e-9 * length => {
+                last.1 = last.1.min(r);
+                last.2 = last.2.max(r);
+            }
+            _ => groups.push((x, r, r)),
+        }
+    }
+    let middle = |k: usize| 0.5 * (groups[k].1 + groups[k].2);
+    let outline: Vec<(f64, f64, f64)> = (0..groups.len())
+        .map(|k| {
+            let (x, low, high) = groups[k];
+            let nearer = |target: f64| if (low - target).abs() <= (high - target).abs() { low } else { high };
+            let left = if k > 0 { nearer(middle(k - 1)) } else { high };
+            let right = if k + 1 < groups.len() { nearer(middle(k + 1)) } else { high };
+            (x, left, right)
+        })
+        .collect();
+
+    let mut stations: Vec<f64> = (0..STATIONS)
+        .map(|i| {
+            let x = length * i as f64 / (STATIONS - 1) as f64;
+            let k = outline.partition_point(|p| p.0 < x).clamp(1, outline.len() - 1);
+            let ((xa, _, ra), (xb, rb, rb_max)) = (outline[k - 1], outline[k]);
+            if x >= xb {
+                rb.max(rb_max)
+            } else {
+                ra + (rb - ra) * (x - xa) / (xb - xa)
+            }
+        })
+        .collect();
+
+    // The nose is the thinner end.
+    let tenth = STATIONS / 10;
+    let front: f64 = stations[..tenth].iter().sum();
+    let back: f64 = stations[STATIONS - tenth..].iter().sum();
+    if front > back {
+        stations.reverse();
+        Ok((length, stations, x0 + length, -1.0))
+    } else {
+        Ok((length, stations, x0, 1.0))
+    }
+}
+
+/// Downhill simplex minimization of `cost` from `start`, with initial
+/// steps `scale`.
+fn nelder_mead(cost: impl Fn(&[f64]) -> f64, start: &[f64], scale: &[f64]) -> Vec<f64> {
+    let n = start.len();
+    let mut simplex: Vec<Vec<f64>> = vec![start.to_vec()];
+    for i in 0..n {
+        let mut vertex = start.to_vec();
+        vertex[i] += scale[i];
+        simplex.push(vertex);
+    }
+    let mut values: Vec<f64> = simplex.iter().map(|v| cost(v)).collect();
+
+    for _ in 0..ITERATIONS {
+        let mut order: Vec<usize> = (0..=n).collect();
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+        simplex = order.iter().map(|&i| simplex[i].clone()).collect();
+        values = order.iter().map(|&i| values[i]).collect();
+
+        let centroid: Vec<f64> = (0..n).map(|j| simplex[..n].iter().map(|v| v[j]).sum::<f64>() / n as f64).collect();
+        let toward = |t: f64| -> Vec<f64> {
+            (0..n).map(|j| centroid[j] + t * (simplex[n][j] - centroid[j])).collect()
+        };
+
+        let reflected = toward(-1.0);
+        let reflected_value = cost(&reflected);
+        if reflected_value < values[0] {
+            let expanded = toward(-2.0);
+            let expanded_value = cost(&expanded);
+            if expanded_value < reflected_value {
+                simplex[n] = expanded;
+                values[n] = expanded_value;
+            } else {
+                simplex[n] = reflected;
+                values[n] = reflected_value;
+            }
+        } else if reflected_value < values[n - 1] {
+            simplex[n] = reflected;
+            values[n] = reflected_value;
+        } else {
+            let contracted = toward(0.5);
+            let contracted_value = cost(&contracted);
+            if contracted_value < values[n] {
+                simplex[n] = contracted;
+                values[n] = contracted_value;
+            } else {
+                for i in 1..=n {
+                    simplex[i] = (0..n).map(|j| 0.5 * (simplex[0][j] + simplex[i][j])).collect();
+                    values[i] = cost(&simplex[i]);
+                }
+            }
+        }
+    }
+
+    let best = (0..=n).min_by(|&a, &b| values[a].total_cmp(&values[b])).unwrap_or(0);
+    simplex[best].clone()
+}
+
+/// Segments a measured outline and fits the MCDRAG geometry to it. The
+/// bearing surface (the longest cylinder) sets the reference diameter, a
+/// shorter cylinder proud of it is the band, and the nose length, RT/R,
+/// meplat, boattail length and base diameter are then adjusted together
+/// to minimize the squared radius error over everything but the band.
+/// Points are in mm; the result keeps the given boundary layer code and
+/// identification and leaves the center of gravity to be computed.
+pub fn fit_profile(
+    points: &[(f64, f64)],
+    boundary_layer: BoundaryLayer,
+    identification: &str,
+) -> Result<ProfileFit, String> {
+    fit_sampled_profile(points, 0.0, boundary_layer, identification)
+}
+
+/// `fit_profile` for outlines measured no finer than `resolution` (in the
+/// units of the points): radius steps smaller than that are not taken as
+/// the edge of the body or of a band.
+pub(crate) fn fit_sampled_profile(
+    points: &[(f64, f64)],
+    resolution: f64,
+    boundary_layer: BoundaryLayer,
+    identification: &str,
+) -> Result<ProfileFit, String> {
+    let (length, stations, origin, direction) = resample(points)?;
+    let dx = length / (STATIONS - 1) as f64;
+    let x_at = |i: usize| i as f64 * dx;
+
+    let slope: Vec<f64> = (0..STATIONS)
+        .map(|i| {
+            let a = i.saturating_sub(SLOPE_WINDOW);
+            let b = (i + SLOPE_WINDOW).min(STATIONS - 1);
+            (stations[b] - stations[a]) / (x_at(b) - x_at(a))
+        })
+        .collect();
+    let r_max = stations.iter().cloned().fold(0.0, f64::max);
+    let flat: Vec<usize> = (0..STATIONS)
+        .filter(|&i| slope[i].abs() < FLAT_SLOPE && stations[i] > 0.3 * r_max)
+        .collect();
+    if flat.is_empty() {
+        return Err("No cylindrical bearing surface found in the profile".to_string());
+    }
+
+    // Bearing radius: the most populated radius level among flat stations.
+    let bin = 0.002 * r_max;
+    let level = |i: usize| (stations[i] / bin).round() as i64;
+    let mut counts = std::collections::HashMap::new();
+    for &i in &flat {
+        *counts.entry(level(i)).or_insert(0usize) += 1;
+    }
+    let mode = counts.iter().max_by_key(|(level, count)| (**count, -**level)).map(|(level, _)| *level).unwrap_or(0);
+    let mut bearing: Vec<f64> = flat.iter().filter(|&&i| (level(i) - mode).abs() <= 1).map(|&i| stations[i]).collect();
+    bearing.sort_by(f64::total_cmp);
+    let body = bearing[bearing.len() / 2];
+    let d = 2.0 * body;
+
+    let tolerance = (body * BODY_TOLERANCE).max(resolution);
+    let on_body = |i: usize| stations[i] >= body - tolerance;
+    let first = (0..STATIONS).find(|&i| on_body(i)).unwrap_or(0);
+    let last = (0..STATIONS).rev().find(|&i| on_body(i)).unwrap_or(STATIONS - 1);
+
+    // Band: the longest run of flat stations standing proud of the body.
+    let proud = |i: usize| stations[i] > body + tolerance && slope[i].abs() < FLAT_SLOPE;
+    let mut band_run: Option<(usize, usize)> = None;
+    let mut i = first;
+    while i <= last {
+        if proud(i) {
+            let start = i;
+            while i <= last && stations[i] > body + tolerance {
+                i += 1;
+            }
+            if band_run.is_none_or(|(a, b)| i - start > b - a) {
+                band_run = Some((start, i - 1));
+            }
+        }
+        i += 1;
+    }
+    let band_diameter = band_run.map_or(1.0, |(a, b)| {
+        let mut radii: Vec<f64> = stations[a..=b].to_vec();
+        radii.sort_by(f64::total_cmp);
+        radii[radii.len() / 2] / body
+    });
+    // Extend the band over any stations on its sides still proud of the body.
+    let band_stations = band_run.map(|(a, b)| {
+        let proud = |i: usize| stations[i] > body + tolerance;
+        let start = (first..a).rev().take_while(|&i| proud(i)).last().unwrap_or(a);
+        let end = (b + 1..=last).take_while(|&i| proud(i)).last().unwrap_or(b);
+        (start, end)
+    });
+    // Edges of the band lie halfway between stations.
+    let band = band_stations.map(|(a, b)| BandPosition {
+        location: (x_at(a) - 0.5 * dx) / d,
+        width: (x_at(b) - x_at(a) + dx) / d,
+    });
+    let in_band = |i: usize| band_stations.is_some_and(|(a, b)| i + 1 >= a && i <= b + 1);
+
+    // Starting values from the segmentation.
+    let total_length = length / d;
+    let nose_length = x_at(first) / d;
+    let mut boattail_length = (length - x_at(last)) / d;
+    let mut base_diameter = 1.0;
+    if boattail_length < MIN_BOATTAIL {
+        boattail_length = 0.0;
+    } else {
+        // Least-squares line through the middle of the boattail.
+        let a = last + (STATIONS - 1 - last) / 10;
+        let b = STATIONS - 1 - (STATIONS - 1 - last) / 10;
+        let n = (b - a + 1) as f64;
+        let (sx, sr) = (a..=b).fold((0.0, 0.0), |(sx, sr), i| (sx + x_at(i), sr + stations[i]));
+        let (mx, mr) = (sx / n, sr / n);
+        let (sxx, sxr) = (a..=b).fold((0.0, 0.0), |(sxx, sxr), i| {
+            (sxx + (x_at(i) - mx).powi(2), sxr + (x_at(i) - mx) * (stations[i] - mr))
+        });
+        let slope = if sxx > 0.0 { sxr / sxx } else { 0.0 };
+        base_diameter = 2.0 * (mr + slope * (length - mx)) / d;
+    }
+    let meplat_diameter = 2.0 * stations[0] / d;
+
+    let shape = |p: &[f64]| ProjectileInput {
+        ref_diameter: d,
+        total_length,
+        nose_length: p[0],
+        rt_r: p[1],
+        boattail_length: if p.len() > 3 { p[3] } else { 0.0 },
+        base_diameter: if p.len() > 3 { p[4] } else { 1.0 },
+        meplat_diameter: p[2],
+        band_diameter,
+        cg_location: 0.0,
+        boundary_layer,
+        identification: identification.to_string(),
+        compound_nose: None,
+        nose_tip: None,
+        bands: Vec::new(),
+        surface: None,
+        tip_cavity: None,
+        base_shape: None,
+        base_bleed: None,
+        rocket_motor: None,
+        fins: None,
+        boattail_shape: None,
+        projectile_type: ProjectileType::Slender,
+    };
+    let cost = |p: &[f64]| {
+        let candidate = shape(p);
+        let valid = p[0] > 0.0
+            && (0.0..=1.0).contains(&p[1])
+            && (0.0..0.9).contains(&p[2])
+            && (p.len() < 4 || (p[3] > 0.0 && p[4] > 0.0 && p[4] < 1.0 && p[0] + p[3] < total_length));
+        if !valid {
+            return f64::INFINITY;
+        }
+        (0..STATIONS)
+            .filter(|&i| !in_band(i))
+            .map(|i| (candidate.radius_at(x_at(i) / d) * d - stations[i]).powi(2))
+            .sum::<f64>()
+    };
+
+    let mut start = vec![nose_length, 0.5, meplat_diameter.min(0.8)];
+    let mut scale = vec![0.1 * nose_length.max(0.5), 0.2, 0.05];
+    if boattail_length > 0.0 {
+        start.extend([boattail_length, base_diameter.clamp(0.5, 0.99)]);
+        scale.extend([0.1 * boattail_length, 0.02]);
+    }
+    // Restart once from the first result to escape a collapsed simplex.
+    let first_pass = nelder_mead(cost, &start, &scale);
+    let best = nelder_mead(cost, &first_pass, &scale);
+    let input = shape(&best);
+
+    let residual = |i: usize| {
+        let x = x_at(i) / d;
+        let fitted = match band {
+            Some(ref band) if x >= band.location && x <= band.location + band.width => 0.5 * band_diameter,
+            _ => input.radius_at(x),
+        };
+        stations[i] - fitted * d
+    };
+    let boattail_start = input.total_length - input.boattail_length;
+    let mut spans = vec![(SegmentKind::Meplat, 0.0, 0.0), (SegmentKind::Ogive, 0.0, input.nose_length)];
+    match band {
+        Some(ref band) => spans.extend([
+            (SegmentKind::Bearing, input.nose_length, band.location),
+            (SegmentKind::Band, band.location, band.location + band.width),
+            (SegmentKind::Bearing, band.location + band.width, boattail_start),
+        ]),
+        None => spans.push((SegmentKind::Bearing, input.nose_length, boattail_start)),
+    }
+    if input.boattail_length > 0.0 {
+        spans.push((SegmentKind::Boattail, boattail_start, input.total_length));
+    }
+
+    let segments = spans
+        .into_iter()
+        .map(|(kind, start, end)| {
+            let errors: Vec<f64> = if kind == SegmentKind::Meplat {
+                vec![stations[0] - 0.5 * input.meplat_diameter * d]
+            } else {
+                (0..STATIONS)
+                    .filter(|&i| x_at(i) / d >= start && x_at(i) / d <= end)
+                    .map(residual)
+                    .collect()
+            };
+            let n = errors.len().max(1) as f64;
+            SegmentFit {
+                kind,
+                start,
+                end,
+                rms_residual: (errors.iter().map(|e| e * e).sum::<f64>() / n).sqrt(),
+                max_residual: errors.iter().fold(0.0, |m: f64, e| m.max(e.abs())),
+            }
+        })
+        .collect();
+    let all: Vec<f64> = (0..STATIONS).map(residual).collect();
+
+    Ok(ProfileFit {
+        input,
+        origin,
+        direction,
+        band,
+        segments,
+        rms_residual: (all.iter().map(|e| e * e).sum::<f64>() / STATIONS as f64).sqrt(),
+        max_residual: all.iter().fold(0.0, |m: f64, e| m.max(e.abs())),
+    })
+}
+
+/// Parses and fits a measured profile.
+pub fn import_profile(request: &ProfileImportRequest) -> Result<ProfileFit, String> {
+    let points = match request.format {
+        ProfileFormat::Csv => parse_csv(&request.data)?,
+        ProfileFormat::Dxf => parse_dxf(&request.data)?,
+    };
+    let scale = request.units.to_mm();
+    let points: Vec<(f64, f64)> = points.iter().map(|&(x, r)| (x * scale, r * scale)).collect();
+    fit_profile(&points, request.boundary_layer, &request.identification)
+}
+
+impl ProfileFit {
+    pub fn to_text(&self) -> String {
+        let input = &self.input;
+        let mut out = String::new();
+        out.push_str("FITTED MCDRAG INPUTS\n\n");
+        out.push_str(&format!("REFERENCE DIAMETER  {:8.3} MM\n", input.ref_diameter));
+        out.push_str(&format!("TOTAL LENGTH        {:8.3} CAL\n", input.total_length));
+        out.push_str(&format!("NOSE LENGTH         {:8.3} CAL\n", input.nose_length));
+        out.push_str(&format!("RT/R                {:8.3}\n", input.rt_r));
+        out.push_str(&format!("BOATTAIL LENGTH     {:8.3} CAL\n", input.boattail_length));
+        out.push_str(&format!("BASE DIAMETER       {:8.3} CAL\n", input.base_diameter));
+        out.push_str(&format!("MEPLAT DIAMETER     {:8.3} CAL\n", input.meplat_diameter));
+        out.push_str(&format!("BAND DIAMETER       {:8.3} CAL\n", input.band_diameter));
+        if let Some(band) = &self.band {
+            out.push_str(&format!("BAND POSITION       {:8.3} TO {:.3} CAL\n", band.location, band.location + band.width));
+        }
+        out.push_str("\n SEGMENT     FROM     TO     RMS ERROR  MAX ERROR\n");
+        out.push_str("            (CAL)   (CAL)      (MM)       (MM)\n");
+        for segment in &self.segments {
+            let name = match segment.kind {
+                SegmentKind::Meplat => "MEPLAT",
+                SegmentKind::Ogive => "OGIVE",
+                SegmentKind::Bearing => "BEARING",
+                SegmentKind::Band => "BAND",
+                SegmentKind::Boattail => "BOATTAIL",
+            };
+            out.push_str(&format!(
+                " {:<9} {:6.3}  {:6.3}   {:8.4}   {:8.4}\n",
+                name, segment.start, segment.end, segment.rms_residual, segment.max_residual
+            ));
+        }
+        out.push_str(&format!(
+            " OVERALL                    {:8.4}   {:8.4}\n",
+            self.rms_residual, self.max_residual
+        ));
+        out
+    }
+}
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+use crate::atmosphere::Atmosphere;
+use crate::standard_drag;
+use crate::trajectory;
+use crate::ProjectileInput;
+
+const FEET_PER_METER: f64 = 3.280839895;
+const NEWTONS_PER_POUND_FORCE: f64 = 4.448221615;
+/// Velocity loss distance: 100 m in metric units, 100 yd in imperial.
+const METERS_PER_100_YARDS: f64 = 91.44;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum VelocityUnit {
+    MetersPerSecond,
+    FeetPerSecond,
+}
+
+/// Velocity sweep for a retardation table. Mass in grams, velocities in
+/// `unit`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetardationInput {
+    pub mass: f64,
+    pub velocity_min: f64,
+    pub velocity_max: f64,
+    pub velocity_step: f64,
+    pub unit: VelocityUnit,
+    #[serde(default)]
+    pub atmosphere: Atmosphere,
+}
+
+/// One velocity of a retardation table. In metric units force is in N,
+/// deceleration in m/s^2 and the velocity loss is over the next 100 m; in
+/// imperial units lbf, ft/s^2 and the next 100 yd.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetardationRow {
+    pub velocity: f64,
+    pub mach: f64,
+    pub cd0: f64,
+    pub drag_force: f64,
+    pub deceleration: f64,
+    pub velocity_loss: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetardationTable {
+    pub unit: VelocityUnit,
+    pub sectional_density: f64,
+    pub rows: Vec<RetardationRow>,
+}
+
+/// Drag force, deceleration and velocity decay versus velocity from the
+/// MCDRAG CD0 curve, for flat fire in the given atmosphere. The velocity
+/// loss is integrated over the loss distance rather than extrapolated from
+/// the local deceleration, so it follows the drag rise through transonic.
+pub fn retardation_table(input: &ProjectileInput, params: &RetardationInput) -> Result<RetardationTable, String> {
+    if params.mass <= 0.0 || input.ref_diameter <= 0.0 {
+        return Err("Mass and reference diameter must be positive".to_string());
+    }
+    if params.velocity_step <= 0.0 || params.velocity_min <= 0.0 || params.velocity_max < params.velocity_min {
+        return Err("Velocity range must be positive and increasing".to_string());
+    }
+
+    let drag = input.calculate_drag_coefficients();
+    let mass = params.mass / 1000.0;
+    let d = input.ref_diameter / 1000.0;
+    let area = 0.25 * std::f64::consts::PI * d * d;
+    let density = params.atmosphere.density();
+    let sound = params.atmosphere.speed_of_sound();
+
+    let (to_si, loss_distance) = match params.unit {
+        VelocityUnit::MetersPerSecond => (1.0, 100.0),
+        VelocityUnit::FeetPerSecond => (1.0 / FEET_PER_METER, METERS_PER_100_YARDS),
+    };
+    let deceleration_at = |v: f64| {
+        let cd = trajectory::cd0_at(&drag, v / sound);
+        density * area * cd * v * v / (2.0 * mass)
+    };
+
+    let mut rows = Vec::new();
+    let mut velocity = params.velocity_min;
+    while velocity <= params.velocity_max + 1e-9 {
+        let v = velocity * to_si;
+        let cd0 = trajectory::cd0_at(&drag, v / sound);
+        let deceleration = deceleration_at(v);
+
+        // dV/dx = -a / V, integrated in 1 m steps with the midpoint rule.
+        let steps = loss_distance.round() as usize;
+        let dx = loss_distance / steps as f64;
+        let mut end = v;
+        for _ in 0..steps {
+            let half = end - 0.5 * dx * deceleration_at(end) / end;
+            end -= dx * deceleration_at(half) / half;
+            if end <= 0.0 {
+                end = 0.0;
+                break;
+            }
+        }
+
+        let (drag_force, deceleration, velocity_loss) = match params.unit {
+            VelocityUnit::MetersPerSecond => (mass * deceleration, deceleration, v - end),
+            VelocityUnit::FeetPerSecond => (
+                mass * deceleration / NEWTONS_PER_POUND_FORCE,
+                deceleration * FEET_PER_METER,
+                (v - end) * FEET_PER_METER,
+            ),
+        };
+        rows.push(RetardationRow {
+            velocity,
+            mach: v / sound,
+            cd0,
+            drag_force,
+            deceleration,
+            velocity_loss,
+        });
+        velocity += params.velocity_step;
+    }
+
+    Ok(RetardationTable {
+        unit: params.unit,
+        sectional_density: standard_drag::sectional_density(params.mass, input.ref_diameter),
+        rows,
+    })
+}
+
+impl RetardationTable {
+    pub fn to_text(&self) -> String {
+        let (velocity, force, deceleration, loss) = match self.unit {
+            VelocityUnit::MetersPerSecond => ("(M/S)", " (N) ", "(M/S2)", "(M/S PER 100 M)"),
+            VelocityUnit::FeetPerSecond => ("(FT/S)", "(LBF)", "(FT/S2)", "(FT/S PER 100 YD)"),
+        };
+        let mut out = String::new();
+        out.push_str(&format!("SECTIONAL DENSITY: {:6.4} LB/IN2\n\n", self.sectional_density));
+        out.push_str(" VELOCITY   MACH    CD0     DRAG    DECEL.   VELOCITY LOSS\n");
+        out.push_str(&format!(" {:>8}                  {:>6}  {:>8}   {}\n\n", velocity, force, deceleration, loss));
+        for row in &self.rows {
+            out.push_str(&format!(
+                "{:8.0} {:7.3} {:7.3} {:8.3} {:9.1} {:10.1}\n",
+                row.velocity, row.mach, row.cd0, row.drag_force, row.deceleration, row.velocity_loss
+            ));
+        }
+        out
+    }
+}
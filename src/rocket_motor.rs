@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectileInput;
+
+/// Ratio of specific heats of the exhaust.
+const JET_GAMMA: f64 = 1.25;
+
+/// Fraction by which a jet filling the whole base would deepen the base
+/// pressure deficit around it by entrainment.
+const ENTRAINMENT: f64 = 0.5;
+
+/// Share of the gap to ambient pressure the base recovers per unit of
+/// jet area share times exit overpressure, as an under-expanded plume
+/// swells and shields the base like a flare.
+const PLUME: f64 = 2.0;
+
+/// Rocket motor of a rocket-assisted projectile: nozzle exit diameter in
+/// calibers, exit Mach number, chamber-to-ambient pressure ratio, and the
+/// ignition delay and burn time in seconds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RocketMotor {
+    pub exit_diameter: f64,
+    pub exit_mach: f64,
+    pub pressure_ratio: f64,
+    #[serde(default)]
+    pub ignition_time: f64,
+    pub burn_time: f64,
+}
+
+impl RocketMotor {
+    /// Nozzle exit static pressure over ambient, isentropic from the
+    /// chamber.
+    pub fn exit_pressure_ratio(&self) -> f64 {
+        let expansion = 1.0 + 0.5 * (JET_GAMMA - 1.0) * self.exit_mach * self.exit_mach;
+        self.pressure_ratio / expansion.powf(JET_GAMMA / (JET_GAMMA - 1.0))
+    }
+
+    /// Whether the nozzle fits the base and the jet is supersonic.
+    pub fn fits(&self, base_diameter: f64) -> bool {
+        self.exit_diameter > 0.0
+            && self.exit_diameter < base_diameter
+            && self.exit_mach >= 1.0
+            && self.pressure_ratio > 0.0
+            && self.ignition_time >= 0.0
+            && self.burn_time > 0.0
+    }
+}
+
+impl ProjectileInput {
+    /// Base pressure ratio and base drag at `mach` with the motor burning,
+    /// in place of McCoy's correlation. The jet takes the centre of the
+    /// base and deepens the pressure deficit of the jet-off base (`pb_off`)
+    /// around it by entrainment, in proportion to its share of the base
+    /// area. An under-expanded jet then raises it back towards, but
+    /// not past, ambient. Only the annulus counts as base drag; the exit
+    /// plane belongs to the thrust.
+    pub fn jet_on_base_drag(&self, motor: &RocketMotor, mach: f64, pb_off: f64) -> (f64, f64) {
+        let base_diameter = self.effective_base_diameter();
+        let share = (motor.exit_diameter / base_diameter).clamp(0.0, 1.0).powi(2);
+        let pumped = 1.0 - (1.0 - pb_off) * (1.0 + ENTRAINMENT * share);
+        let plume = (PLUME * share * (motor.exit_pressure_ratio() - 1.0)).clamp(0.0, 1.0);
+        let pb_pinf = pumped + (1.0 - pumped).max(0.0) * plume;
+        let annulus = (base_diameter * base_diameter - motor.exit_diameter * motor.exit_diameter).max(0.0);
+        (pb_pinf, 1.4286 * (1.0 - pb_pinf) * annulus / (mach * mach))
+    }
+}
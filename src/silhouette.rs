@@ -0,0 +1,362 @@
+use serde::{Deserialize, Serialize};
+
+use crate::profile_fit::{self, ProfileFit};
+use crate::BoundaryLayer;
+
+/// How pixels are converted to mm: the known diameter of the bearing
+/// surface, or a scale bar of `length` mm measured as `pixels` long.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ImageScale {
+    ReferenceDiameter { diameter: f64 },
+    ScaleBar { length: f64, pixels: f64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SilhouetteRequest {
+    pub scale: ImageScale,
+    pub boundary_layer: BoundaryLayer,
+    #[serde(default)]
+    pub identification: String,
+}
+
+/// A fitted silhouette. `axis_angle` is the direction from nose to base in
+/// the image (degrees, counterclockwise on screen from the +x axis) and
+/// `asymmetry` the RMS offset (mm) of the outline midline from that axis.
+/// `overlay` is a PNG of the image with the fitted shape drawn on it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SilhouetteFit {
+    pub mm_per_pixel: f64,
+    pub axis_angle: f64,
+    pub asymmetry: f64,
+    pub fit: ProfileFit,
+    #[serde(skip)]
+    pub overlay: Vec<u8>,
+}
+
+/// 8-bit grayscale image.
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+/// Decodes a PNG, or a binary or ASCII PGM/PPM, to grayscale. Transparent
+/// PNG pixels count as white background.
+fn decode(bytes: &[u8]) -> Result<Image, String> {
+    if bytes.starts_with(b"\x89PNG") {
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let mut reader = decoder.read_info().map_err(|e| format!("Invalid PNG: {}", e))?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).map_err(|e| format!("Invalid PNG: {}", e))?;
+        let channels = info.color_type.samples();
+        let gray = |p: &[u8]| -> u8 {
+            let (value, alpha) = match p.len() {
+                1 => (p[0] as f64, 255.0),
+                2 => (p[0] as f64, p[1] as f64),
+                3 => (0.299 * p[0] as f64 + 0.587 * p[1] as f64 + 0.114 * p[2] as f64, 255.0),
+                _ => (0.299 * p[0] as f64 + 0.587 * p[1] as f64 + 0.114 * p[2] as f64, p[3] as f64),
+            };
+            (value * alpha / 255.0 + 255.0 - alpha).round() as u8
+        };
+        let pixels = buffer[..info.buffer_size()]
+            .chunks(info.line_size)
+            .flat_map(|row| row[..info.width as usize * channels].chunks(channels).map(gray).collect::<Vec<_>>())
+            .collect();
+        return Ok(Image { width: info.width as usize, height: info.height as usize, pixels });
+    }
+
+    // Netpbm: magic, width, height, maxval, then samples.
+    let mut at = 0;
+    let mut token = || -> Option<String> {
+        while at < bytes.len() {
+            if bytes[at] == b'#' {
+                while at < bytes.len() && bytes[at] != b'\n' {
+                    at += 1;
+                }
+            } else if bytes[at].is_ascii_whitespace() {
+                at += 1;
+            } else {
+                break;
+            }
+        }
+        let start = at;
+        while at < bytes.len() && !bytes[at].is_ascii_whitespace() {
+            at += 1;
+        }
+        (at > start).then(|| String::from_utf8_lossy(&bytes[start..at]).into_owned())
+    };
+    let magic = token().ok_or("Image is neither PNG nor PPM/PGM")?;
+    let channels = match magic.as_str() {
+        "P2" | "P5" => 1,
+        "P3" | "P6" => 3,
+        _ => return Err("Image is neither PNG nor PPM/PGM".to_string()),
+    };
+    let mut number = || token().and_then(|t| t.parse::<usize>().ok()).ok_or("Bad PPM/PGM header".to_string());
+    let (width, height, maxval) = (number()?, number()?, number()?);
+    if maxval == 0 || maxval > 255 {
+        return Err("Only 8-bit PPM/PGM images are supported".to_string());
+    }
+    if width == 0 || height == 0 {
+        return Err("PPM/PGM image has no pixels".to_string());
+    }
+    let count = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(channels))
+        .ok_or("PPM/PGM image is too large")?;
+    let samples: Vec<usize> = if magic == "P5" || magic == "P6" {
+        let end = (at + 1).checked_add(count).ok_or("PPM/PGM image is too large")?;
+        let data = bytes.get(at + 1..end).ok_or("PPM/PGM data is truncated")?;
+        data.iter().map(|&b| b as usize).collect()
+    } else {
+        (0..count).map(|_| number()).collect::<Result<_, _>>()?
+    };
+    let scale = |v: usize| (v * 255 / maxval) as f64;
+    let pixels = samples
+        .chunks(channels)
+        .map(|p| match p.len() {
+            1 => scale(p[0]) as u8,
+            _ => (0.299 * scale(p[0]) + 0.587 * scale(p[1]) + 0.114 * scale(p[2])).round() as u8,
+        })
+        .collect();
+    Ok(Image { width, height, pixels })
+}
+
+/// Otsu's threshold: the gray level that best separates the histogram
+/// into two classes.
+fn otsu(image: &Image) -> u8 {
+    let mut histogram = [0usize; 256];
+    for &p in &image.pixels {
+        histogram[p as usize] += 1;
+    }
+    let total = image.pixels.len() as f64;
+    let sum: f64 = histogram.iter().enumerate().map(|(i, &n)| i as f64 * n as f64).sum();
+    let (mut weight, mut partial, mut best, mut level) = (0.0, 0.0, 0.0, 0);
+    for (i, &n) in histogram.iter().enumerate() {
+        weight += n as f64;
+        partial += i as f64 * n as f64;
+        if weight == 0.0 || weight == total {
+            continue;
+        }
+        let (mean_low, mean_high) = (partial / weight, (sum - partial) / (total - weight));
+        let between = weight * (total - weight) * (mean_low - mean_high).powi(2);
+        if between > best {
+            best = between;
+            level = i;
+        }
+    }
+    level as u8
+}
+
+/// Pixels of the largest 4-connected region on the other side of the
+/// threshold from the image border, which is taken to be background.
+fn silhouette_pixels(image: &Image) -> Result<Vec<(usize, usize)>, String> {
+    let threshold = otsu(image);
+    let (w, h) = (image.width, image.height);
+    let border = (0..w).flat_map(|x| [(x, 0), (x, h - 1)]).chain((0..h).flat_map(|y| [(0, y), (w - 1, y)]));
+    let (dark, count) = border.fold((0, 0), |(dark, count), (x, y)| {
+        (dark + (image.pixels[y * w + x] <= threshold) as usize, count + 1)
+    });
+    let dark_background = 2 * dark > count;
+    let foreground = |i: usize| (image.pixels[i] <= threshold) != dark_background;
+
+    let mut seen = vec![false; w * h];
+    let mut best: Vec<usize> = Vec::new();
+    for start in 0..w * h {
+        if seen[start] || !foreground(start) {
+            continue;
+        }
+        seen[start] = true;
+        let mut region = vec![start];
+        let mut next = 0;
+        while next < region.len() {
+            let i = region[next];
+            next += 1;
+            let (x, y) = (i % w, i / w);
+            let neighbours = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < w).then(|| i + 1),
+                (y > 0).then(|| i - w),
+                (y + 1 < h).then(|| i + w),
+            ];
+            for j in neighbours.into_iter().flatten() {
+                if !seen[j] && foreground(j) {
+                    seen[j] = true;
+                    region.push(j);
+                }
+            }
+        }
+        if region.len() > best.len() {
+            best = region;
+        }
+    }
+    if best.len() < 100 {
+        return Err("No projectile silhouette found in the image".to_string());
+    }
+    Ok(best.into_iter().map(|i| (i % w, i / w)).collect())
+}
+
+/// Cross sections of the silhouette normal to the axis through `centre`
+/// along `(ux, uy)`: for each one-pixel station, its position, the offset
+/// of the section midpoint from the axis and the half-width. The half-width
+/// is the half-span of the outermost pixel centres plus one edge allowance
+/// for the whole outline, chosen so the sections add up to the pixel count.
+/// This keeps the width unbiased whatever the tilt of the axis against the
+/// pixel grid, while flat faces stay sharp.
+fn sections(pixels: &[(usize, usize)], centre: (f64, f64), (ux, uy): (f64, f64)) -> Vec<(f64, f64, f64)> {
+    let project = |&(x, y): &(usize, usize)| {
+        let (dx, dy) = (x as f64 - centre.0, y as f64 - centre.1);
+        (dx * ux + dy * uy, -dx * uy + dy * ux)
+    };
+    let t_min = pixels.iter().map(|p| project(p).0).fold(f64::INFINITY, f64::min);
+    let t_max = pixels.iter().map(|p| project(p).0).fold(f64::NEG_INFINITY, f64::max);
+    let bins = (t_max - t_min).round() as usize + 1;
+    let mut range = vec![(f64::INFINITY, f64::NEG_INFINITY); bins];
+    for p in pixels {
+        let (t, s) = project(p);
+        let bin = (t - t_min).round() as usize;
+        range[bin] = (range[bin].0.min(s), range[bin].1.max(s));
+    }
+    let range: Vec<(usize, (f64, f64))> =
+        range.into_iter().enumerate().filter(|(_, (low, high))| high >= low).collect();
+    let span: f64 = range.iter().map(|(_, (low, high))| high - low).sum();
+    let allowance = 0.5 * (pixels.len() as f64 - span) / range.len() as f64;
+    range
+        .into_iter()
+        .map(|(i, (low, high))| (t_min + i as f64, 0.5 * (low + high), 0.5 * (high - low) + allowance))
+        .collect()
+}
+
+/// Traces the silhouette of a backlit projectile, makes the profile
+/// symmetric about its axis and fits the MCDRAG geometry to it. The axis
+/// starts as the principal axis of the silhouette and is then tilted and
+/// shifted onto the straight line through the outline midpoints.
+pub fn import_silhouette(bytes: &[u8], request: &SilhouetteRequest) -> Result<SilhouetteFit, String> {
+    let image = decode(bytes)?;
+    let pixels = silhouette_pixels(&image)?;
+
+    let n = pixels.len() as f64;
+    let (mx, my) = pixels.iter().fold((0.0, 0.0), |(sx, sy), &(x, y)| (sx + x as f64, sy + y as f64));
+    let mut centre = (mx / n, my / n);
+    let (sxx, sxy, syy) = pixels.iter().fold((0.0, 0.0, 0.0), |(a, b, c), &(x, y)| {
+        let (dx, dy) = (x as f64 - centre.0, y as f64 - centre.1);
+        (a + dx * dx, b + dx * dy, c + dy * dy)
+    });
+    let mut angle = 0.5 * (2.0 * sxy).atan2(sxx - syy);
+
+    let mut rows = Vec::new();
+    for _ in 0..3 {
+        let axis = (angle.cos(), angle.sin());
+        rows = sections(&pixels, centre, axis);
+        // Straight line through the midpoints, ignoring the tapering ends.
+        let middle = &rows[rows.len() / 10..rows.len() - rows.len() / 10];
+        let k = middle.len() as f64;
+        let (st, sm) = middle.iter().fold((0.0, 0.0), |(st, sm), r| (st + r.0, sm + r.1));
+        let (mt, mm) = (st / k, sm / k);
+        let (stt, stm) = middle.iter().fold((0.0, 0.0), |(a, b), r| {
+            (a + (r.0 - mt).powi(2), b + (r.0 - mt) * (r.1 - mm))
+        });
+        let tilt = if stt > 0.0 { stm / stt } else { 0.0 };
+        let offset = mm - tilt * mt;
+        centre = (centre.0 - offset * axis.1, centre.1 + offset * axis.0);
+        angle += tilt.atan();
+    }
+
+    let points: Vec<(f64, f64)> = rows.iter().map(|&(t, _, radius)| (t, radius)).collect();
+    let midline: Vec<f64> = rows.iter().map(|&(_, offset, _)| offset).collect();
+    // Where the outline crosses pixel rows the radius jitters by up to half
+    // a pixel, so nothing less than a pixel proud is taken for a band.
+    let mut fit = profile_fit::fit_sampled_profile(&points, 1.0, request.boundary_layer, &request.identification)?;
+
+    let mm_per_pixel = match request.scale {
+        ImageScale::ReferenceDiameter { diameter } => diameter / fit.input.ref_diameter,
+        ImageScale::ScaleBar { length, pixels } => length / pixels,
+    };
+    if !(mm_per_pixel.is_finite() && mm_per_pixel > 0.0) {
+        return Err("Image scale must be positive".to_string());
+    }
+    let overlay = draw_overlay(&image, &fit, centre, angle)?;
+
+    fit.input.ref_diameter *= mm_per_pixel;
+    fit.origin *= mm_per_pixel;
+    fit.rms_residual *= mm_per_pixel;
+    fit.max_residual *= mm_per_pixel;
+    for segment in &mut fit.segments {
+        segment.rms_residual *= mm_per_pixel;
+        segment.max_residual *= mm_per_pixel;
+    }
+    let asymmetry = (midline.iter().map(|m| m * m).sum::<f64>() / midline.len() as f64).sqrt() * mm_per_pixel;
+
+    // The image y axis points down, so screen angles run the other way.
+    let nose_to_base = if fit.direction < 0.0 { angle + std::f64::consts::PI } else { angle };
+    let axis_angle = (-nose_to_base.to_degrees()).rem_euclid(360.0);
+
+    Ok(SilhouetteFit {
+        mm_per_pixel,
+        axis_angle,
+        asymmetry,
+        fit,
+        overlay,
+    })
+}
+
+/// The image, lightened, with the body axis in blue and the fitted contour
+/// (band included) in red. `fit` is still in pixels.
+fn draw_overlay(image: &Image, fit: &ProfileFit, centre: (f64, f64), angle: f64) -> Result<Vec<u8>, String> {
+    let (w, h) = (image.width, image.height);
+    let mut rgb: Vec<u8> = image.pixels.iter().flat_map(|&p| [128 + p / 2; 3]).collect();
+    let (ux, uy) = (angle.cos(), angle.sin());
+    let to_image = |t: f64, s: f64| (centre.0 + t * ux - s * uy, centre.1 + t * uy + s * ux);
+    let mut line = |a: (f64, f64), b: (f64, f64), colour: [u8; 3]| {
+        let steps = ((b.0 - a.0).abs().max((b.1 - a.1).abs()).ceil() as usize).max(1);
+        for i in 0..=steps {
+            let f = i as f64 / steps as f64;
+            let (x, y) = (a.0 + f * (b.0 - a.0), a.1 + f * (b.1 - a.1));
+            if x >= 0.0 && y >= 0.0 && (x.round() as usize) < w && (y.round() as usize) < h {
+                let at = 3 * (y.round() as usize * w + x.round() as usize);
+                rgb[at..at + 3].copy_from_slice(&colour);
+            }
+        }
+    };
+
+    let input = &fit.input;
+    let d = input.ref_diameter;
+    let station = |x: f64| fit.origin + fit.direction * x * d;
+    line(to_image(station(-0.2), 0.0), to_image(station(input.total_length + 0.2), 0.0), [0, 0, 255]);
+
+    let steps = (input.total_length * d).ceil() as usize * 2;
+    let radius = |x: f64| match &fit.band {
+        Some(band) if x >= band.location && x <= band.location + band.width => 0.5 * input.band_diameter,
+        _ => input.radius_at(x),
+    };
+    for side in [-1.0, 1.0] {
+        let mut previous = to_image(station(0.0), 0.0);
+        for i in 0..=steps {
+            let x = input.total_length * i as f64 / steps as f64;
+            let point = to_image(station(x), side * radius(x) * d);
+            line(previous, point, [255, 0, 0]);
+            previous = point;
+        }
+        line(previous, to_image(station(input.total_length), 0.0), [255, 0, 0]);
+    }
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, w as u32, h as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| format!("PNG encoding failed: {}", e))?;
+    writer.write_image_data(&rgb).map_err(|e| format!("PNG encoding failed: {}", e))?;
+    writer.finish().map_err(|e| format!("PNG encoding failed: {}", e))?;
+    Ok(out)
+}
+
+impl SilhouetteFit {
+    pub fn to_text(&self) -> String {
+        let mut out = format!(
+            "SILHOUETTE: {:.5} MM PER PIXEL, AXIS AT {:.2} DEG, ASYMMETRY {:.4} MM RMS\n\n",
+            self.mm_per_pixel, self.axis_angle, self.asymmetry
+        );
+        out.push_str(&self.fit.to_text());
+        out
+    }
+}
@@ -0,0 +1,149 @@
+use crate::trajectory::interpolate;
+use crate::{BoundaryLayer, DragCoefficients, ProjectileInput, ProjectileType};
+
+/// Mach numbers of the sphere drag table: McCoy's, with the low speeds of
+/// airsoft and round balls added below Mach 0.5.
+const MACH_NUMBERS: [f64; 30] = [
+    0.1, 0.2, 0.3, 0.4,
+    0.5, 0.6, 0.7, 0.8, 0.85, 0.9, 0.925, 0.95, 0.975, 1.0,
+    1.1, 1.2, 1.3, 1.4, 1.5, 1.6, 1.7, 1.8, 2.0, 2.2,
+    2.5, 3.0, 3.5, 4.0, 4.5, 5.0,
+];
+
+/// Sphere drag against Mach number at subcritical Reynolds numbers: a fair
+/// curve through the free-flight and wind tunnel data of Bailey and Hiatt
+/// and of Miller and Bailey.
+const MACH_DRAG: [(f64, f64); 18] = [
+    (0.0, 0.47), (0.3, 0.47), (0.4, 0.48), (0.5, 0.50), (0.6, 0.53),
+    (0.7, 0.58), (0.8, 0.66), (0.9, 0.78), (1.0, 0.90), (1.1, 0.97),
+    (1.2, 1.01), (1.4, 1.03), (1.6, 1.03), (2.0, 1.00), (2.5, 0.97),
+    (3.0, 0.95), (4.0, 0.93), (5.0, 0.92),
+];
+
+/// Incompressible sphere drag against log10 of the Reynolds number: the
+/// standard drag curve (Schlichting), through the drag crisis of a smooth
+/// sphere.
+const REYNOLDS_DRAG: [(f64, f64); 13] = [
+    (2.0, 1.10), (2.5, 0.70), (3.0, 0.47), (3.5, 0.40), (4.0, 0.41),
+    (4.5, 0.46), (5.0, 0.48), (5.3, 0.46), (5.45, 0.30), (5.6, 0.10),
+    (6.0, 0.13), (6.6, 0.19), (7.0, 0.20),
+];
+
+/// Drag the Mach curve starts from, that of the standard drag curve
+/// between about 3 x 10^4 and 2 x 10^5.
+const SUBCRITICAL_DRAG: f64 = 0.47;
+
+/// Mach numbers over which the Reynolds number effect, the drag crisis
+/// included, fades out: compressibility stops the boundary layer going
+/// turbulent ahead of separation.
+const REYNOLDS_FADE_START: f64 = 0.3;
+const REYNOLDS_FADE_END: f64 = 0.6;
+
+/// Reynolds number at which the drag crisis of a smooth sphere starts.
+const CRISIS_REYNOLDS: f64 = 2.0e5;
+
+/// Angle (degrees from the front) at which the laminar boundary layer of a
+/// sphere separates; the cap ahead of it carries the skin friction.
+const SEPARATION_ANGLE: f64 = 100.0;
+
+impl ProjectileInput {
+    /// Sphere or round ball of `ref_diameter` mm, one caliber long. The
+    /// slender-body inputs give the front half as a tangent ogive nose with
+    /// no boattail; the drag, geometry and exports use the sphere itself.
+    pub fn sphere(ref_diameter: f64, identification: String) -> Self {
+        ProjectileInput {
+            ref_diameter,
+            total_length: 1.0,
+            nose_length: 0.5,
+            rt_r: 1.0,
+            boattail_length: 0.0,
+            base_diameter: 1.0,
+            meplat_diameter: 0.0,
+            band_diameter: 1.0,
+            cg_location: 0.5,
+            boundary_layer: BoundaryLayer::LaminarLaminar,
+            identification,
+            compound_nose: None,
+            nose_tip: None,
+            bands: Vec::new(),
+            surface: None,
+            tip_cavity: None,
+            base_shape: None,
+            base_bleed: None,
+            rocket_motor: None,
+            fins: None,
+            boattail_shape: None,
+            projectile_type: ProjectileType::Sphere,
+        }
+    }
+
+    pub fn is_sphere(&self) -> bool {
+        self.projectile_type == ProjectileType::Sphere
+    }
+
+    /// McCoy's Reynolds number at `mach`, based on the diameter.
+    pub fn sphere_reynolds(&self, mach: f64) -> f64 {
+        23296.3 * mach * self.ref_diameter
+    }
+
+    /// Sphere drag table in place of McCoy's correlations. CD0 follows the
+    /// Mach curve, shifted by the Reynolds number correlation at low speed.
+    /// Of it, CDSF is laminar flat plate friction over the cap ahead of
+    /// separation, CDB the flat base drag at McCoy's base pressure (at most
+    /// the pressure drag) and CDH the rest.
+    pub fn sphere_drag_coefficients(&self) -> Vec<DragCoefficients> {
+        let wetted_area = 0.5 * std::f64::consts::PI * (1.0 - SEPARATION_ANGLE.to_radians().cos());
+        MACH_NUMBERS
+            .iter()
+            .map(|&mach| {
+                let m2 = mach * mach;
+                let fade = ((REYNOLDS_FADE_END - mach) / (REYNOLDS_FADE_END - REYNOLDS_FADE_START)).clamp(0.0, 1.0);
+                let cd0 = interpolate(&MACH_DRAG, mach, |p| p.0, |p| p.1)
+                    + (interpolate(&REYNOLDS_DRAG, self.sphere_reynolds(mach).log10(), |p| p.0, |p| p.1)
+                        - SUBCRITICAL_DRAG)
+                        * fade;
+
+                let (c7, _) = self.friction_coefficients_at(mach, 1.0);
+                let cdsf = 1.2732 * c7 * wetted_area;
+                let p2 = if mach < 1.0 {
+                    1.0 / (1.0 + 0.1875 * m2 + 0.0531 * m2 * m2)
+                } else {
+                    1.0 / (1.0 + 0.2477 * m2 + 0.0345 * m2 * m2)
+                };
+                let cdb = (1.4286 * (1.0 - p2) / m2).min((cd0 - cdsf).max(0.0));
+
+                DragCoefficients {
+                    mach,
+                    cd0,
+                    cdh: cd0 - cdsf - cdb,
+                    cdsf,
+                    cdbnd: 0.0,
+                    cdgrv: 0.0,
+                    cdeng: 0.0,
+                    cdfsf: 0.0,
+                    cdfw: 0.0,
+                    cdfb: 0.0,
+                    cdbt: 0.0,
+                    cdb,
+                    pb_pinf: 1.0 - cdb * m2 / 1.4286,
+                }
+            })
+            .collect()
+    }
+
+    /// Warns when the ball is in the drag crisis at low speed, where the
+    /// drag depends on surface roughness and free stream turbulence.
+    pub fn sphere_diagnostics(&self) -> Vec<String> {
+        let mut diagnostics = Vec::new();
+        let crisis = MACH_NUMBERS
+            .iter()
+            .any(|&mach| mach < REYNOLDS_FADE_END && self.sphere_reynolds(mach) > CRISIS_REYNOLDS);
+        if crisis {
+            diagnostics.push(format!(
+                "SPHERE REYNOLDS NUMBER IN THE DRAG CRISIS BELOW MACH {}. CD0 DEPENDS ON SURFACE FINISH AND MAY BE INCORRECT.",
+                REYNOLDS_FADE_END
+            ));
+        }
+        diagnostics
+    }
+}
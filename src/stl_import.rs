@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+
+use crate::profile_fit::{self, LengthUnit, ProfileFit};
+use crate::BoundaryLayer;
+
+/// Planes the mesh is cut with along the detected axis.
+const SLICES: usize = 1000;
+
+type Vector = [f64; 3];
+type Triangle = [Vector; 3];
+
+/// Inputs an STL cannot carry. The mesh itself is passed as bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeshImportRequest {
+    #[serde(default)]
+    pub units: LengthUnit,
+    pub boundary_layer: BoundaryLayer,
+    #[serde(default)]
+    pub identification: String,
+}
+
+/// A fitted mesh: the symmetry axis found (unit vector through the surface
+/// centroid, in mesh coordinates) and the profile fit along it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MeshFit {
+    pub triangles: usize,
+    pub centroid: Vector,
+    pub axis: Vector,
+    pub fit: ProfileFit,
+}
+
+fn sub(a: Vector, b: Vector) -> Vector {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: Vector, b: Vector) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vector, b: Vector) -> Vector {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+/// Reads an ASCII or binary STL.
+pub fn parse_stl(bytes: &[u8]) -> Result<Vec<Triangle>, String> {
+    let binary_length = |count: usize| 84 + 50 * count;
+    if bytes.len() >= 84 {
+        let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+        if bytes.len() == binary_length(count) {
+            let float = |at: usize| f32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]) as f64;
+            let triangles = (0..count)
+                .map(|i| {
+                    // Each record is a normal, three vertices and an attribute word.
+                    let at = 84 + 50 * i + 12;
+                    let vertex = |v: usize| [float(at + 12 * v), float(at + 12 * v + 4), float(at + 12 * v + 8)];
+                    [vertex(0), vertex(1), vertex(2)]
+                })
+                .collect::<Vec<_>>();
+            return if triangles.is_empty() { Err("STL contains no facets".to_string()) } else { Ok(triangles) };
+        }
+    }
+
+    let text = std::str::from_utf8(bytes).map_err(|_| "STL is neither valid binary nor ASCII".to_string())?;
+    let vertices: Vec<Vector> = text
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| line.starts_with("vertex"))
+        .map(|line| {
+            let values: Vec<f64> = line.split_whitespace().skip(1).filter_map(|v| v.parse().ok()).collect();
+            if values.len() == 3 {
+                Ok([values[0], values[1], values[2]])
+            } else {
+                Err(format!("Bad STL vertex line: {}", line))
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    if vertices.is_empty() || !vertices.len().is_multiple_of(3) {
+        return Err("STL contains no complete facets".to_string());
+    }
+    Ok(vertices.chunks(3).map(|v| [v[0], v[1], v[2]]).collect())
+}
+
+/// Eigenvalues and eigenvectors (as columns) of a symmetric 3x3 matrix by
+/// Jacobi rotations.
+fn eigen(mut a: [[f64; 3]; 3]) -> ([f64; 3], [[f64; 3]; 3]) {
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for _ in 0..50 {
+        let (mut p, mut q) = (0, 1);
+        for (i, j) in [(0, 2), (1, 2)] {
+            if a[i][j].abs() > a[p][q].abs() {
+                p = i;
+                q = j;
+            }
+        }
+        let scale = a[0][0].abs() + a[1][1].abs() + a[2][2].abs();
+        if a[p][q].abs() <= 1e-15 * scale {
+            break;
+        }
+        let theta = 0.5 * (2.0 * a[p][q]).atan2(a[q][q] - a[p][p]);
+        let (s, c) = theta.sin_cos();
+        for row in a.iter_mut() {
+            let (x, y) = (row[p], row[q]);
+            row[p] = c * x - s * y;
+            row[q] = s * x + c * y;
+        }
+        let (row_p, row_q) = (a[p], a[q]);
+        a[p] = [0, 1, 2].map(|k| c * row_p[k] - s * row_q[k]);
+        a[q] = [0, 1, 2].map(|k| s * row_p[k] + c * row_q[k]);
+        for row in v.iter_mut() {
+            let (x, y) = (row[p], row[q]);
+            row[p] = c * x - s * y;
+            row[q] = s * x + c * y;
+        }
+    }
+    ([a[0][0], a[1][1], a[2][2]], v)
+}
+
+/// Surface centroid and symmetry axis. The second moment of the surface
+/// area of a body of revolution has two equal principal values; the axis is
+/// the principal direction whose value stands apart from the other two.
+fn symmetry_axis(triangles: &[Triangle]) -> Result<(Vector, Vector), String> {
+    let mut area = 0.0;
+    let mut first = [0.0; 3];
+    let mut second = [[0.0; 3]; 3];
+    for &[a, b, c] in triangles {
+        let n = cross(sub(b, a), sub(c, a));
+        let da = 0.5 * dot(n, n).sqrt();
+        let s = [a[0] + b[0] + c[0], a[1] + b[1] + c[1], a[2] + b[2] + c[2]];
+        area += da;
+        for i in 0..3 {
+            first[i] += da * s[i] / 3.0;
+            for j in 0..3 {
+                // Integral of x x^T over a triangle: A/12 (sum v v^T + s s^T).
+                second[i][j] += da / 12.0 * (a[i] * a[j] + b[i] * b[j] + c[i] * c[j] + s[i] * s[j]);
+            }
+        }
+    }
+    if area <= 0.0 {
+        return Err("STL has no surface area".to_string());
+    }
+
+    let centroid = [first[0] / area, first[1] / area, first[2] / area];
+    let mut moment = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            moment[i][j] = second[i][j] / area - centroid[i] * centroid[j];
+        }
+    }
+    let (values, vectors) = eigen(moment);
+    let distinct = |k: usize| (0..3).filter(|&i| i != k).map(|i| (values[k] - values[i]).abs()).fold(f64::INFINITY, f64::min);
+    let k = (0..3).max_by(|&a, &b| distinct(a).total_cmp(&distinct(b))).unwrap_or(0);
+    let axis = [vectors[0][k], vectors[1][k], vectors[2][k]];
+    let norm = dot(axis, axis).sqrt();
+    Ok((centroid, [axis[0] / norm, axis[1] / norm, axis[2] / norm]))
+}
+
+/// Radius profile (station along the axis, distance from it) of the mesh:
+/// every vertex, plus the points where evenly spaced planes normal to the
+/// axis cut the triangle edges.
+fn radius_profile(triangles: &[Triangle], centroid: Vector, axis: Vector) -> Vec<(f64, f64)> {
+    let project = |p: Vector| {
+        let offset = sub(p, centroid);
+        let t = dot(offset, axis);
+        let radial = [offset[0] - t * axis[0], offset[1] - t * axis[1], offset[2] - t * axis[2]];
+        (t, dot(radial, radial).sqrt())
+    };
+
+    let projected: Vec<[(f64, f64, Vector); 3]> = triangles
+        .iter()
+        .map(|tri| {
+            tri.map(|p| {
+                let (t, r) = project(p);
+                (t, r, p)
+            })
+        })
+        .collect();
+    let (t_min, t_max) = projected
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v.0), hi.max(v.0)));
+    let spacing = (t_max - t_min) / SLICES as f64;
+
+    let mut points: Vec<(f64, f64)> = projected.iter().flatten().map(|v| (v.0, v.1)).collect();
+    for tri in &projected {
+        for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+            let (lo, hi) = if a.0 <= b.0 { (a, b) } else { (b, a) };
+            if hi.0 - lo.0 <= 0.0 {
+                continue;
+            }
+            let first = ((lo.0 - t_min) / spacing).ceil() as usize;
+            let last = (((hi.0 - t_min) / spacing).floor() as usize).min(SLICES);
+            for k in first..=last {
+                let t = t_min + k as f64 * spacing;
+                let f = (t - lo.0) / (hi.0 - lo.0);
+                let p = [
+                    lo.2[0] + f * (hi.2[0] - lo.2[0]),
+                    lo.2[1] + f * (hi.2[1] - lo.2[1]),
+                    lo.2[2] + f * (hi.2[2] - lo.2[2]),
+                ];
+                points.push(project(p));
+            }
+        }
+    }
+    points
+}
+
+/// Finds the symmetry axis of an STL mesh, slices it into a radius profile
+/// and fits the MCDRAG geometry to that profile.
+pub fn import_stl(bytes: &[u8], request: &MeshImportRequest) -> Result<MeshFit, String> {
+    let scale = request.units.to_mm();
+    let triangles: Vec<Triangle> = parse_stl(bytes)?
+        .into_iter()
+        .map(|tri| tri.map(|p| [p[0] * scale, p[1] * scale, p[2] * scale]))
+        .collect();
+    let (centroid, axis) = symmetry_axis(&triangles)?;
+    let points = radius_profile(&triangles, centroid, axis);
+    let fit = profile_fit::fit_profile(&points, request.boundary_layer, &request.identification)?;
+
+    Ok(MeshFit {
+        triangles: triangles.len(),
+        centroid,
+        axis,
+        fit,
+    })
+}
+
+impl MeshFit {
+    pub fn to_text(&self) -> String {
+        let mut out = format!(
+            "STL MESH: {} FACETS, AXIS ({:.4}, {:.4}, {:.4}) THROUGH ({:.3}, {:.3}, {:.3}) MM\n\n",
+            self.triangles, self.axis[0], self.axis[1], self.axis[2], self.centroid[0], self.centroid[1], self.centroid[2]
+        );
+        out.push_str(&self.fit.to_text());
+        out
+    }
+}
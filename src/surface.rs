@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+use crate::bands::band_factor;
+use crate::ProjectileInput;
+
+/// Width-to-depth ratio at which a groove stops being bridged by the flow
+/// over it and behaves as an open cavity: a drop and a step of its depth.
+const OPEN_CAVITY_RATIO: f64 = 10.0;
+
+/// Share of the bearing surface circumference covered by land engravings;
+/// lands and grooves are about equally wide in most rifling.
+const ENGRAVED_FRACTION: f64 = 0.5;
+
+/// Surface features MCDRAG leaves out: cannelures and crimp grooves, and
+/// the land engravings a bullet carries after firing. Depths and widths in
+/// calibers; a count of 0 omits the feature. The drag does not depend on
+/// where the grooves are; `groove_location` (start of the first groove,
+/// calibers from the nose tip, 0 if not known) and `groove_spacing`
+/// (between groove starts) place them for drawings and models.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct SurfaceFeatures {
+    #[serde(default)]
+    pub groove_count: u32,
+    #[serde(default)]
+    pub groove_depth: f64,
+    #[serde(default)]
+    pub groove_width: f64,
+    #[serde(default)]
+    pub groove_location: f64,
+    #[serde(default)]
+    pub groove_spacing: f64,
+    #[serde(default)]
+    pub engraving_count: u32,
+    #[serde(default)]
+    pub engraving_depth: f64,
+}
+
+impl SurfaceFeatures {
+    /// Whether every feature that is present has positive dimensions.
+    pub fn is_valid(&self) -> bool {
+        (self.groove_count == 0 || (self.groove_depth > 0.0 && self.groove_width > 0.0))
+            && (self.engraving_count == 0 || self.engraving_depth > 0.0)
+    }
+
+    /// Start of each groove in calibers from the nose tip, or none when
+    /// the grooves are not placed.
+    pub fn groove_starts(&self) -> Vec<f64> {
+        if self.groove_location <= 0.0 {
+            return Vec::new();
+        }
+        (0..self.groove_count)
+            .map(|i| self.groove_location + i as f64 * self.groove_spacing)
+            .collect()
+    }
+}
+
+impl ProjectileInput {
+    /// Groove and engraving drag increments (CDGRV, CDENG) at `mach`, given
+    /// the skin friction coefficient `afterbody_cf` of the smooth
+    /// afterbody.
+    ///
+    /// A groove's downstream wall is a step of its depth, carrying McCoy's
+    /// band term for that height once the groove is wide enough to be an
+    /// open cavity, and a proportionally smaller share when it is bridged.
+    /// Engravings roughen the bearing surface: the engraved share of it has
+    /// the fully rough flat plate friction (Schlichting) for a roughness of
+    /// the engraving depth, and the walls of the engravings add wetted
+    /// area at the smooth friction.
+    pub fn surface_drag_at(&self, mach: f64, afterbody_cf: f64) -> (f64, f64) {
+        let Some(surface) = self.surface else {
+            return (0.0, 0.0);
+        };
+
+        let cdgrv = if surface.groove_count > 0 && surface.groove_depth > 0.0 {
+            let openness = (surface.groove_width / (OPEN_CAVITY_RATIO * surface.groove_depth)).min(1.0);
+            surface.groove_count as f64 * band_factor(mach) * 2.0 * surface.groove_depth * openness
+        } else {
+            0.0
+        };
+
+        let bearing = (self.total_length - self.nose_length - self.boattail_length).max(0.0);
+        let cdeng = if surface.engraving_count > 0 && surface.engraving_depth > 0.0 {
+            let rough_cf = (2.87 + 1.58 * (self.total_length / surface.engraving_depth).log10()).powf(-2.5)
+                * (1.0 + 0.21 * mach * mach).powf(-0.32);
+            let roughness = (rough_cf - afterbody_cf).max(0.0) * std::f64::consts::PI * bearing * ENGRAVED_FRACTION;
+            let walls = afterbody_cf * 2.0 * surface.engraving_depth * surface.engraving_count as f64 * bearing;
+            4.0 / std::f64::consts::PI * (roughness + walls)
+        } else {
+            0.0
+        };
+
+        (cdgrv, cdeng)
+    }
+}
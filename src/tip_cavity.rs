@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectileInput;
+
+/// Pressure a deep cavity carries relative to McCoy's flat meplat: the
+/// flat face averages 0.85 of the pitot pressure in his supersonic term,
+/// while the stagnated air filling a cavity carries all of it.
+const STAGNATED_CAVITY: f64 = 1.0 / 0.85;
+
+/// Depth-to-diameter ratio beyond which the cavity is fully stagnated.
+const STAGNATED_DEPTH: f64 = 0.5;
+
+/// Cavities deeper than this many diameters can resonate like a Hartmann
+/// tube, making the bow shock oscillate. The narrow pits of match open tips
+/// below `RESONANT_DIAMETER` calibers are too small to move it.
+const RESONANT_DEPTH: f64 = 1.0;
+const RESONANT_DIAMETER: f64 = 0.1;
+
+/// Cavity diameter (calibers) beyond which the bow shock stands off the
+/// cavity rather than the nose, as on large hollow points.
+const LARGE_CAVITY: f64 = 0.25;
+
+/// Open-tip or hollow-point cavity in the meplat, diameter and depth in
+/// calibers.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TipCavity {
+    pub diameter: f64,
+    pub depth: f64,
+}
+
+impl TipCavity {
+    /// Whether the cavity fits inside the meplat.
+    pub fn fits(&self, meplat_diameter: f64) -> bool {
+        self.diameter > 0.0 && self.depth > 0.0 && self.diameter < meplat_diameter
+    }
+
+    /// Face drag of the cavity mouth relative to the same area of flat
+    /// meplat, rising from 1 for a shallow dish to the stagnated value.
+    pub fn face_factor(&self) -> f64 {
+        let depth = (self.depth / (STAGNATED_DEPTH * self.diameter)).min(1.0);
+        1.0 + (STAGNATED_CAVITY - 1.0) * depth
+    }
+}
+
+impl ProjectileInput {
+    /// Share of the meplat face area open to the cavity.
+    pub fn cavity_area_fraction(&self) -> f64 {
+        match self.tip_cavity {
+            Some(cavity) if self.meplat_diameter > 0.0 => (cavity.diameter / self.meplat_diameter).powi(2).min(1.0),
+            _ => 0.0,
+        }
+    }
+
+    /// Warns when the cavity is deep enough to resonate or wide enough to
+    /// set the flow over the nose rather than follow it.
+    pub fn cavity_diagnostics(&self) -> Vec<String> {
+        let mut diagnostics = Vec::new();
+        if let Some(cavity) = self.tip_cavity {
+            if cavity.diameter >= RESONANT_DIAMETER && cavity.depth > RESONANT_DEPTH * cavity.diameter {
+                diagnostics.push("TIP CAVITY DEEPER THAN ITS DIAMETER MAY RESONATE. SUPERSONIC CDH MAY BE INCORRECT.".to_string());
+            }
+            if cavity.diameter > LARGE_CAVITY {
+                diagnostics.push(format!(
+                    "TIP CAVITY WIDER THAN {} CALIBER CHANGES THE NOSE FLOW. CDH MAY BE INCORRECT.",
+                    LARGE_CAVITY
+                ));
+            }
+        }
+        diagnostics
+    }
+}
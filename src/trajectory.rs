@@ -0,0 +1,537 @@
+use serde::{Deserialize, Serialize};
+
+use crate::aero::AeroCoefficients;
+use crate::atmosphere::Atmosphere;
+use crate::geometry::MassProperties;
+use crate::{DragCoefficients, ProjectileInput};
+
+const GRAVITY: f64 = 9.80665;
+const EARTH_RATE: f64 = 7.292115e-5;
+const TIME_STEP: f64 = 0.0005;
+const IMPACT_TIME_STEP: f64 = 0.002;
+const MAX_TIME: f64 = 120.0;
+pub(crate) const MOA_PER_RADIAN: f64 = 10800.0 / std::f64::consts::PI;
+const MPH_PER_MPS: f64 = 2.236936;
+
+/// Firing conditions for a modified point-mass trajectory. Mass in grams,
+/// velocities in m/s, ranges in meters, angles in degrees. Twist is in
+/// calibers per turn and negative for a left-hand twist. Wind direction is
+/// the direction the wind blows from, measured clockwise from the line of
+/// fire (0 = headwind, 90 = from the right). Azimuth is measured clockwise
+/// from true north. Sight height is in millimeters above the bore; a zero
+/// range of 0 fires at `departure_angle` instead of solving for a zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrajectoryInput {
+    pub mass: f64,
+    pub muzzle_velocity: f64,
+    pub twist: f64,
+    #[serde(default)]
+    pub departure_angle: f64,
+    #[serde(default)]
+    pub sight_height: f64,
+    #[serde(default)]
+    pub zero_range: f64,
+    pub range_step: f64,
+    pub max_range: f64,
+    #[serde(default)]
+    pub wind_speed: f64,
+    #[serde(default)]
+    pub wind_direction: f64,
+    #[serde(default)]
+    pub latitude: f64,
+    #[serde(default)]
+    pub azimuth: f64,
+    #[serde(default)]
+    pub atmosphere: Atmosphere,
+}
+
+/// One line of a range card. Drops and deflections are in meters relative
+/// to the line of sight (drop positive down, windage positive right), the
+/// elevation and windage columns are the corrections needed to hit, and
+/// the yaw of repose is in degrees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeCardRow {
+    pub range: f64,
+    pub velocity: f64,
+    pub mach: f64,
+    pub energy: f64,
+    pub time: f64,
+    pub drop: f64,
+    pub elevation_moa: f64,
+    pub elevation_mil: f64,
+    pub windage: f64,
+    pub windage_moa: f64,
+    pub windage_mil: f64,
+    pub wind_drift: f64,
+    pub spin_drift: f64,
+    pub coriolis_horizontal: f64,
+    pub coriolis_vertical: f64,
+    pub crosswind_jump: f64,
+    pub yaw_of_repose: f64,
+    pub spin_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeCard {
+    pub stability_factor: f64,
+    pub muzzle_spin_rate: f64,
+    pub departure_angle: f64,
+    pub rows: Vec<RangeCardRow>,
+}
+
+/// Drag coefficient at `mach`, interpolated linearly in a drag table and
+/// held constant beyond its ends.
+pub fn cd0_at(drag: &[DragCoefficients], mach: f64) -> f64 {
+    interpolate(drag, mach, |row| row.mach, |row| row.cd0)
+}
+
+pub(crate) fn interpolate<T>(table: &[T], x: f64, key: impl Fn(&T) -> f64, value: impl Fn(&T) -> f64) -> f64 {
+    let first = &table[0];
+    if x <= key(first) {
+        return value(first);
+    }
+    for pair in table.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        if x <= key(b) {
+            let t = (x - key(a)) / (key(b) - key(a));
+            return value(a) + t * (value(b) - value(a));
+        }
+    }
+    value(&table[table.len() - 1])
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    (a[0] * a[0] + a[1] * a[1] + a[2] * a[2]).sqrt()
+}
+
+/// State vector: position (x downrange, y up, z right), velocity, spin rate.
+type State = [f64; 7];
+
+/// Point along a trajectory, sampled at a fixed downrange distance.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Sample {
+    pub range: f64,
+    pub height: f64,
+    pub deflection: f64,
+    pub velocity: f64,
+    pub time: f64,
+    pub spin: f64,
+    pub yaw: f64,
+}
+
+/// Everything the equations of motion need that does not change in flight.
+struct Model<'a> {
+    drag: &'a [DragCoefficients],
+    power_off: Option<((f64, f64), &'a [DragCoefficients])>,
+    aero: &'a [AeroCoefficients],
+    density: f64,
+    sound: f64,
+    area: f64,
+    diameter: f64,
+    mass: f64,
+    axial_inertia: f64,
+    wind: [f64; 3],
+    earth_rate: [f64; 3],
+}
+
+impl Model<'_> {
+    /// Yaw of repose vector, McCoy's modified point-mass approximation with
+    /// the acceleration taken as gravity alone (drag is parallel to the
+    /// airspeed and drops out of the cross product).
+    fn yaw_of_repose(&self, air: [f64; 3], speed: f64, spin: f64, cm_alpha: f64) -> [f64; 3] {
+        if cm_alpha.abs() < 1e-9 || speed <= 0.0 {
+            return [0.0; 3];
+        }
+        let turn = cross(air, [0.0, -GRAVITY, 0.0]);
+        let scale = -2.0 * self.axial_inertia * spin
+            / (self.density * self.area * self.diameter * cm_alpha * speed.powi(4));
+        [scale * turn[0], scale * turn[1], scale * turn[2]]
+    }
+
+    /// Drag table in force `time` seconds into the flight.
+    fn drag_at(&self, time: f64) -> &[DragCoefficients] {
+        match self.power_off {
+            Some(((start, end), drag)) if time < start || time >= end => drag,
+            _ => self.drag,
+        }
+    }
+
+    fn derivative(&self, s: &State, time: f64) -> (State, f64) {
+        let velocity = [s[3], s[4], s[5]];
+        let air = [
+            velocity[0] - self.wind[0],
+            velocity[1] - self.wind[1],
+            velocity[2] - self.wind[2],
+        ];
+        let speed = norm(air);
+        let mach = speed / self.sound;
+
+        let cl_alpha = interpolate(self.aero, mach, |a| a.mach, |a| a.cl_alpha);
+        let cm_alpha = interpolate(self.aero, mach, |a| a.mach, |a| a.cm_alpha);
+        let clp = interpolate(self.aero, mach, |a| a.mach, |a| a.clp);
+        let cd_delta2 = interpolate(self.aero, mach, |a| a.mach, |a| a.cd_delta2);
+
+        let yaw = self.yaw_of_repose(air, speed, s[6], cm_alpha);
+        let cd = cd0_at(self.drag_at(time), mach) + cd_delta2 * norm(yaw).powi(2);
+        let k = self.density * self.area / (2.0 * self.mass);
+        let coriolis = cross(self.earth_rate, velocity);
+
+        let mut d = [0.0; 7];
+        d[0] = velocity[0];
+        d[1] = velocity[1];
+        d[2] = velocity[2];
+        for i in 0..3 {
+            d[3 + i] = -k * cd * speed * air[i] + k * cl_alpha * speed * speed * yaw[i]
+                - 2.0 * coriolis[i];
+        }
+        d[4] -= GRAVITY;
+        d[6] = self.density * self.area * self.diameter * self.diameter * speed * clp * s[6]
+            / (2.0 * self.axial_inertia);
+
+        (d, norm(yaw))
+    }
+
+    fn step(&self, s: &State, dt: f64, time: f64) -> State {
+        let add = |a: &State, b: &State, h: f64| {
+            let mut out = *a;
+            for i in 0..7 {
+                out[i] += h * b[i];
+            }
+            out
+        };
+        let (k1, _) = self.derivative(s, time);
+        let (k2, _) = self.derivative(&add(s, &k1, 0.5 * dt), time + 0.5 * dt);
+        let (k3, _) = self.derivative(&add(s, &k2, 0.5 * dt), time + 0.5 * dt);
+        let (k4, _) = self.derivative(&add(s, &k3, dt), time + dt);
+        let mut out = *s;
+        for i in 0..7 {
+            out[i] += dt / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+        }
+        out
+    }
+
+    /// Integrates from `initial` and samples the trajectory every
+    /// `range_step` meters downrange up to `max_range`.
+    fn fly(&self, initial: State, range_step: f64, max_range: f64) -> Vec<Sample> {
+        let mut samples = Vec::new();
+        let mut state = initial;
+        let mut time = 0.0;
+        let mut next = range_step;
+
+        while next <= max_range + 1e-9 && time < MAX_TIME && norm([state[3], state[4], state[5]]) > 1.0 {
+            let after = self.step(&state, TIME_STEP, time);
+            while next <= max_range + 1e-9 && after[0] >= next {
+                let t = (next - state[0]) / (after[0] - state[0]);
+                let lerp = |i: usize| state[i] + t * (after[i] - state[i]);
+                let (_, yaw) = self.derivative(&state, time);
+                samples.push(Sample {
+                    range: next,
+                    height: lerp(1),
+                    deflection: lerp(2),
+                    velocity: norm([lerp(3), lerp(4), lerp(5)]),
+                    time: time + t * TIME_STEP,
+                    spin: lerp(6),
+                    yaw,
+                });
+                next += range_step;
+            }
+            state = after;
+            time += TIME_STEP;
+        }
+
+        samples
+    }
+}
+
+/// Quantities shared by every flight of one projectile and atmosphere.
+pub(crate) struct Setup {
+    drag: Vec<DragCoefficients>,
+    power_off: Option<((f64, f64), Vec<DragCoefficients>)>,
+    aero: Vec<AeroCoefficients>,
+    density: f64,
+    sound: f64,
+    area: f64,
+    diameter: f64,
+    mass: f64,
+    twist: f64,
+    sight_height: f64,
+    properties: MassProperties,
+    wind: [f64; 3],
+    earth_rate: [f64; 3],
+}
+
+impl Setup {
+    /// Prepares flights of `input` under `params` using the drag table
+    /// `drag`, normally `input.calculate_drag_coefficients()`, switching to
+    /// its power-off version outside the burn of a rocket motor or base
+    /// bleed.
+    pub(crate) fn new(
+        input: &ProjectileInput,
+        params: &TrajectoryInput,
+        drag: Vec<DragCoefficients>,
+    ) -> Result<Setup, String> {
+        if input.ref_diameter <= 0.0 || input.total_length <= 0.0 || input.nose_length <= 0.0 {
+            return Err("Projectile diameter, length and nose length must be positive".to_string());
+        }
+        if params.mass <= 0.0 || params.muzzle_velocity <= 0.0 {
+            return Err("Mass and muzzle velocity must be positive".to_string());
+        }
+        if params.twist == 0.0 && !input.is_sphere() {
+            return Err("Twist rate must not be zero".to_string());
+        }
+
+        if drag.is_empty() {
+            return Err("Drag table is empty".to_string());
+        }
+        let mass = params.mass / 1000.0;
+        let diameter = input.ref_diameter / 1000.0;
+        let aero = input.aero_coefficients(&drag, input.effective_cg());
+
+        let from = params.wind_direction.to_radians();
+        let latitude = params.latitude.to_radians();
+        let azimuth = params.azimuth.to_radians();
+
+        Ok(Setup {
+            power_off: input.power_off_drag(&drag),
+            drag,
+            aero,
+            density: params.atmosphere.density(),
+            sound: params.atmosphere.speed_of_sound(),
+            area: 0.25 * std::f64::consts::PI * diameter * diameter,
+            diameter,
+            mass,
+            twist: params.twist,
+            sight_height: params.sight_height / 1000.0,
+            properties: input.mass_properties(mass),
+            wind: [
+                -params.wind_speed * from.cos(),
+                0.0,
+                -params.wind_speed * from.sin(),
+            ],
+            earth_rate: [
+                EARTH_RATE * latitude.cos() * azimuth.cos(),
+                EARTH_RATE * latitude.sin(),
+                -EARTH_RATE * latitude.cos() * azimuth.sin(),
+            ],
+        })
+    }
+
+    fn model(&self, wind: bool, coriolis: bool) -> Model<'_> {
+        Model {
+            drag: &self.drag,
+            power_off: self.power_off.as_ref().map(|(window, drag)| (*window, drag.as_slice())),
+            aero: &self.aero,
+            density: self.density,
+            sound: self.sound,
+            area: self.area,
+            diameter: self.diameter,
+            mass: self.mass,
+            axial_inertia: self.properties.axial_inertia,
+            wind: if wind { self.wind } else { [0.0; 3] },
+            earth_rate: if coriolis { self.earth_rate } else { [0.0; 3] },
+        }
+    }
+
+    /// Muzzle spin rate (rad/s) for a given muzzle velocity; none from a
+    /// smoothbore (zero twist).
+    fn spin(&self, velocity: f64) -> f64 {
+        if self.twist == 0.0 {
+            return 0.0;
+        }
+        2.0 * std::f64::consts::PI * velocity / (self.twist * self.diameter)
+    }
+
+    /// Launch state with the bore `angle` radians above the line of sight,
+    /// which starts `sight_height` above the bore.
+    fn initial(&self, velocity: f64, angle: f64) -> State {
+        [
+            0.0,
+            -self.sight_height,
+            0.0,
+            velocity * angle.cos(),
+            velocity * angle.sin(),
+            0.0,
+            self.spin(velocity),
+        ]
+    }
+
+    pub(crate) fn stability_factor(&self, velocity: f64) -> f64 {
+        let spin = self.spin(velocity);
+        let cm_alpha = interpolate(&self.aero, velocity / self.sound, |a| a.mach, |a| a.cm_alpha);
+        if cm_alpha.abs() < 1e-9 {
+            return f64::INFINITY;
+        }
+        self.properties.axial_inertia.powi(2) * spin * spin
+            / (2.0 * self.density * self.properties.transverse_inertia * self.area * self.diameter
+                * velocity * velocity * cm_alpha)
+    }
+
+    /// Bore angle (radians) that puts the trajectory on the line of sight at
+    /// `zero_range`, in still air.
+    pub(crate) fn zero_angle(&self, velocity: f64, zero_range: f64) -> Result<f64, String> {
+        let model = self.model(false, false);
+        let mut angle = 0.0;
+        for _ in 0..20 {
+            let sample = model.fly(self.initial(velocity, angle), zero_range, zero_range);
+            let height = match sample.first() {
+                Some(sample) => sample.height,
+                None => return Err(format!("Zero range of {} m is out of reach", zero_range)),
+            };
+            angle -= (height / zero_range).atan();
+            if height.abs() < 1e-6 {
+                break;
+            }
+        }
+        Ok(angle)
+    }
+
+    /// Still-air flight zeroed at `zero_range` (or with a level bore when it
+    /// is 0), sampled every `range_step` meters up to `max_range`.
+    pub(crate) fn still_flight(
+        &self,
+        velocity: f64,
+        zero_range: f64,
+        range_step: f64,
+        max_range: f64,
+    ) -> Result<Vec<Sample>, String> {
+        let angle = if zero_range > 0.0 {
+            self.zero_angle(velocity, zero_range)?
+        } else {
+            0.0
+        };
+        Ok(self.model(false, false).fly(self.initial(velocity, angle), range_step, max_range))
+    }
+
+    /// Flies in still air until the projectile falls back through the
+    /// launch height, for indirect-fire tables. The flight is given at
+    /// least the vacuum time of flight; `None` if it lasts longer still.
+    pub(crate) fn impact(&self, velocity: f64, angle: f64) -> Option<Impact> {
+        let model = self.model(false, false);
+        let mut state = self.initial(velocity, angle);
+        state[1] = 0.0;
+        let mut time = 0.0;
+        let mut max_ordinate: f64 = 0.0;
+        let max_time = MAX_TIME.max(2.0 * velocity * angle.sin() / GRAVITY);
+
+        while time < max_time {
+            let after = model.step(&state, IMPACT_TIME_STEP, time);
+            max_ordinate = max_ordinate.max(after[1]);
+            if after[1] < 0.0 && after[4] < 0.0 && time > 0.0 {
+                let t = state[1] / (state[1] - after[1]);
+                let lerp = |i: usize| state[i] + t * (after[i] - state[i]);
+                let (vx, vy, vz) = (lerp(3), lerp(4), lerp(5));
+                return Some(Impact {
+                    range: lerp(0),
+                    deflection: lerp(2),
+                    time: time + t * IMPACT_TIME_STEP,
+                    velocity: norm([vx, vy, vz]),
+                    angle_of_fall: (-vy / vx).atan(),
+                    max_ordinate,
+                });
+            }
+            state = after;
+            time += IMPACT_TIME_STEP;
+        }
+        None
+    }
+}
+
+/// Point of fall of an indirect-fire trajectory.
+pub(crate) struct Impact {
+    pub range: f64,
+    pub deflection: f64,
+    pub time: f64,
+    pub velocity: f64,
+    pub angle_of_fall: f64,
+    pub max_ordinate: f64,
+}
+
+/// Computes a modified point-mass range card: MCDRAG CD0 plus the yaw drag
+/// of the yaw of repose, the geometry-derived lift, overturning moment and
+/// roll damping, spin decay, yaw of repose (spin drift), wind, Coriolis and
+/// the vertical aerodynamic jump caused by a crosswind. With a zero range the bore angle is solved
+/// so the still-air trajectory crosses the line of sight there; otherwise
+/// `departure_angle` is used.
+pub fn range_card(input: &ProjectileInput, params: &TrajectoryInput) -> Result<RangeCard, String> {
+    range_card_with_drag(input, params, input.calculate_drag_coefficients())
+}
+
+/// Same as `range_card`, but flown with an arbitrary drag table such as a
+/// trued curve.
+pub fn range_card_with_drag(
+    input: &ProjectileInput,
+    params: &TrajectoryInput,
+    drag: Vec<DragCoefficients>,
+) -> Result<RangeCard, String> {
+    if params.range_step <= 0.0 || params.max_range < params.range_step {
+        return Err("Range step must be positive and no larger than the maximum range".to_string());
+    }
+
+    let setup = Setup::new(input, params, drag)?;
+    let velocity = params.muzzle_velocity;
+    let stability_factor = setup.stability_factor(velocity);
+    let angle = if params.zero_range > 0.0 {
+        setup.zero_angle(velocity, params.zero_range)?
+    } else {
+        params.departure_angle.to_radians()
+    };
+
+    let initial = setup.initial(velocity, angle);
+    let full = setup.model(true, true).fly(initial, params.range_step, params.max_range);
+    let calm = setup.model(false, true).fly(initial, params.range_step, params.max_range);
+    let still = setup.model(false, false).fly(initial, params.range_step, params.max_range);
+
+    // Litz's empirical aerodynamic jump: MOA per mph of crosswind. A
+    // right-hand twist throws the bullet low in a wind from the left. A
+    // sphere has no overturning moment to turn it.
+    let jump_moa_per_mph = if input.is_sphere() {
+        0.0
+    } else {
+        0.01 * stability_factor - 0.0024 * input.total_length + 0.032
+    };
+    let jump_moa = -params.twist.signum() * jump_moa_per_mph * setup.wind[2] * MPH_PER_MPS;
+
+    let rows = full.iter().zip(calm.iter()).zip(still.iter())
+        .map(|((f, c), s)| {
+            let crosswind_jump = f.range * (jump_moa / MOA_PER_RADIAN).tan();
+            let height = f.height + crosswind_jump;
+            let elevation = -(height / f.range).atan();
+            let windage = -(f.deflection / f.range).atan();
+            RangeCardRow {
+                range: f.range,
+                velocity: f.velocity,
+                mach: f.velocity / setup.sound,
+                energy: 0.5 * setup.mass * f.velocity * f.velocity,
+                time: f.time,
+                drop: -height,
+                elevation_moa: elevation * MOA_PER_RADIAN,
+                elevation_mil: elevation * 1000.0,
+                windage: f.deflection,
+                windage_moa: windage * MOA_PER_RADIAN,
+                windage_mil: windage * 1000.0,
+                wind_drift: f.deflection - c.deflection,
+                spin_drift: s.deflection,
+                coriolis_horizontal: c.deflection - s.deflection,
+                coriolis_vertical: c.height - s.height,
+                crosswind_jump,
+                yaw_of_repose: f.yaw.to_degrees(),
+                spin_rate: f.spin / (2.0 * std::f64::consts::PI),
+            }
+        })
+        .collect();
+
+    Ok(RangeCard {
+        stability_factor,
+        muzzle_spin_rate: setup.spin(velocity) / (2.0 * std::f64::consts::PI),
+        departure_angle: angle.to_degrees(),
+        rows,
+    })
+}
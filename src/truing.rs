@@ -0,0 +1,401 @@
+use serde::{Deserialize, Serialize};
+
+use crate::standard_drag::{self, DragModel};
+use crate::trajectory::{self, Sample, Setup, TrajectoryInput};
+use crate::{DragCoefficients, ProjectileInput};
+
+/// Sampling interval (m) of the truing flights.
+const SAMPLE_STEP: f64 = 1.0;
+const FACTOR_MIN: f64 = 0.25;
+const FACTOR_MAX: f64 = 4.0;
+const ITERATIONS: usize = 40;
+
+/// Something measured on the range. Velocities in m/s, ranges in meters,
+/// drop in meters below the line of sight for the zero and sight height of
+/// the trajectory input. A velocity at range 0 is a muzzle velocity.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Observation {
+    Velocity { range: f64, velocity: f64 },
+    Drop { range: f64, drop: f64 },
+}
+
+impl Observation {
+    fn range(&self) -> f64 {
+        match self {
+            Observation::Velocity { range, .. } | Observation::Drop { range, .. } => *range,
+        }
+    }
+
+    fn value(&self) -> f64 {
+        match self {
+            Observation::Velocity { velocity, .. } => *velocity,
+            Observation::Drop { drop, .. } => *drop,
+        }
+    }
+
+    /// The observation as it appears in error messages.
+    fn describe(&self) -> String {
+        match self {
+            Observation::Velocity { range, velocity } => format!("velocity of {} m/s at {} m", velocity, range),
+            Observation::Drop { range, drop } => format!("drop of {} m at {} m", drop, range),
+        }
+    }
+
+    fn predict(&self, sample: &Sample) -> f64 {
+        match self {
+            Observation::Velocity { .. } => sample.velocity,
+            Observation::Drop { .. } => -sample.height,
+        }
+    }
+}
+
+/// `Scale` multiplies the whole CD0 curve by one factor. `MachDependent`
+/// fits one factor per observation, each applied over the Mach band flown
+/// between the previous observation and that one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TruingMode {
+    Scale,
+    MachDependent,
+}
+
+/// CD0 multiplier applied between two Mach numbers.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MachFactor {
+    pub mach_low: f64,
+    pub mach_high: f64,
+    pub factor: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObservationFit {
+    pub observation: Observation,
+    pub predicted: f64,
+    pub residual: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TruingResult {
+    pub muzzle_velocity: f64,
+    pub factors: Vec<MachFactor>,
+    pub drag: Vec<DragCoefficients>,
+    pub fits: Vec<ObservationFit>,
+    pub bc_g1: f64,
+    pub bc_g7: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TruingRequest {
+    pub trajectory: TrajectoryInput,
+    pub observations: Vec<Observation>,
+    pub mode: TruingMode,
+}
+
+fn factor_at(factors: &[MachFactor], mach: f64) -> f64 {
+    factors.iter()
+        .find(|band| mach >= band.mach_low && mach <= band.mach_high)
+        .or_else(|| if mach > factors[0].mach_high { factors.first() } else { factors.last() })
+        .map_or(1.0, |band| band.factor)
+}
+
+fn lerp_row(a: &DragCoefficients, b: &DragCoefficients, mach: f64) -> DragCoefficients {
+    let t = (mach - a.mach) / (b.mach - a.mach);
+    let lerp = |x: f64, y: f64| x + t * (y - x);
+    DragCoefficients {
+        mach,
+        cd0: lerp(a.cd0, b.cd0),
+        cdh: lerp(a.cdh, b.cdh),
+        cdsf: lerp(a.cdsf, b.cdsf),
+        cdbnd: lerp(a.cdbnd, b.cdbnd),
+        cdgrv: lerp(a.cdgrv, b.cdgrv),
+        cdeng: lerp(a.cdeng, b.cdeng),
+        cdfsf: lerp(a.cdfsf, b.cdfsf),
+        cdfw: lerp(a.cdfw, b.cdfw),
+        cdfb: lerp(a.cdfb, b.cdfb),
+        cdbt: lerp(a.cdbt, b.cdbt),
+        cdb: lerp(a.cdb, b.cdb),
+        pb_pinf: lerp(a.pb_pinf, b.pb_pinf),
+    }
+}
+
+/// Scales a drag table by piecewise-constant Mach factors. Rows are added
+/// just either side of each band edge so the step survives interpolation.
+pub fn apply_factors(drag: &[DragCoefficients], factors: &[MachFactor]) -> Vec<DragCoefficients> {
+    let mut machs: Vec<f64> = drag.iter().map(|row| row.mach).collect();
+    for band in factors {
+        for edge in [band.mach_low, band.mach_high] {
+            if edge.is_finite() && edge > drag[0].mach && edge < drag[drag.len() - 1].mach {
+                machs.push(edge - 1e-6);
+                machs.push(edge + 1e-6);
+            }
+        }
+    }
+    machs.sort_by(|a, b| a.total_cmp(b));
+    machs.dedup();
+
+    machs.iter()
+        .map(|&mach| {
+            let row = match drag.windows(2).find(|w| mach >= w[0].mach && mach <= w[1].mach) {
+                Some(pair) => lerp_row(&pair[0], &pair[1], mach),
+                None => drag[0].clone(),
+            };
+            let f = factor_at(factors, mach);
+            DragCoefficients {
+                cd0: row.cd0 * f,
+                cdh: row.cdh * f,
+                cdsf: row.cdsf * f,
+                cdbnd: row.cdbnd * f,
+                cdgrv: row.cdgrv * f,
+                cdeng: row.cdeng * f,
+                cdfsf: row.cdfsf * f,
+                cdfw: row.cdfw * f,
+                cdfb: row.cdfb * f,
+                cdbt: row.cdbt * f,
+                cdb: row.cdb * f,
+                ..row
+            }
+        })
+        .collect()
+}
+
+struct Truing<'a> {
+    input: &'a ProjectileInput,
+    params: &'a TrajectoryInput,
+    base: Vec<DragCoefficients>,
+    /// Velocity observation the muzzle velocity is solved from, if any.
+    anchor: Option<Observation>,
+    max_range: f64,
+}
+
+impl Truing<'_> {
+    fn flight(&self, drag: Vec<DragCoefficients>, velocity: f64) -> Result<Vec<Sample>, String> {
+        let setup = Setup::new(self.input, self.params, drag)?;
+        setup.still_flight(velocity, self.params.zero_range, SAMPLE_STEP, self.max_range)
+    }
+
+    fn sample_at(samples: &[Sample], range: f64) -> Result<Sample, String> {
+        let index = (range / SAMPLE_STEP).ceil() as usize;
+        if index == 0 {
+            return Err("Observations must be downrange of the muzzle".to_string());
+        }
+        let after = samples.get(index - 1)
+            .ok_or_else(|| format!("Observation at {} m is out of reach", range))?;
+        let before = if index >= 2 { samples[index - 2] } else { *after };
+        if after.range == before.range {
+            return Ok(*after);
+        }
+        let t = (range - before.range) / (after.range - before.range);
+        Ok(Sample {
+            range,
+            height: before.height + t * (after.height - before.height),
+            velocity: before.velocity + t * (after.velocity - before.velocity),
+            ..*after
+        })
+    }
+
+    /// Muzzle velocity that reproduces the anchor observation with `drag`.
+    fn muzzle_velocity(&self, drag: &[DragCoefficients]) -> Result<f64, String> {
+        let anchor = match self.anchor {
+            Some(anchor) => anchor,
+            None => return Ok(self.params.muzzle_velocity),
+        };
+        if anchor.range() <= 0.0 {
+            return Ok(anchor.value());
+        }
+        let mut velocity = self.params.muzzle_velocity.max(anchor.value());
+        for _ in 0..20 {
+            let samples = self.flight(drag.to_vec(), velocity)?;
+            let predicted = Self::sample_at(&samples, anchor.range())?.velocity;
+            velocity *= anchor.value() / predicted;
+            if (predicted - anchor.value()).abs() < 1e-4 {
+                break;
+            }
+        }
+        Ok(velocity)
+    }
+
+    fn predict(&self, factors: &[MachFactor], observations: &[Observation]) -> Result<(f64, Vec<f64>), String> {
+        let drag = apply_factors(&self.base, factors);
+        let velocity = self.muzzle_velocity(&drag)?;
+        let samples = self.flight(drag, velocity)?;
+        let predicted = observations.iter()
+            .map(|obs| Self::sample_at(&samples, obs.range()).map(|s| obs.predict(&s)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((velocity, predicted))
+    }
+}
+
+/// Finds CD0 multipliers that make the still-air trajectory reproduce
+/// chronograph or drop observations. With two or more velocity readings
+/// the first one fixes the muzzle velocity and the rest true the drag;
+/// otherwise the muzzle velocity of the trajectory input is trusted.
+pub fn true_drag_curve(
+    input: &ProjectileInput,
+    params: &TrajectoryInput,
+    observations: &[Observation],
+    mode: TruingMode,
+) -> Result<TruingResult, String> {
+    let mut observations = observations.to_vec();
+    observations.sort_by(|a, b| a.range().total_cmp(&b.range()));
+    let invalid = |obs: &Observation| {
+        obs.range() < 0.0 || (matches!(obs, Observation::Velocity { .. }) && obs.value() <= 0.0)
+    };
+    if observations.iter().any(invalid) {
+        return Err("Observation ranges must not be negative and velocities must be positive".to_string());
+    }
+
+    let velocities: Vec<Observation> = observations.iter()
+        .filter(|obs| matches!(obs, Observation::Velocity { .. }))
+        .cloned()
+        .collect();
+    let anchor = if velocities.len() >= 2 || velocities.first().is_some_and(|obs| obs.range() <= 0.0) {
+        velocities.first().cloned()
+    } else {
+        None
+    };
+    let targets: Vec<Observation> = observations.iter()
+        .filter(|obs| anchor.is_none_or(|a| a.range() != obs.range() || a.value() != obs.value()))
+        .cloned()
+        .collect();
+    if targets.is_empty() {
+        return Err("Need at least one observation besides the muzzle velocity reference".to_string());
+    }
+
+    let truing = Truing {
+        input,
+        params,
+        base: input.calculate_drag_coefficients(),
+        anchor,
+        max_range: observations.last().map_or(0.0, |obs| obs.range()) + SAMPLE_STEP,
+    };
+    let sound = params.atmosphere.speed_of_sound();
+
+    let factors = match mode {
+        TruingMode::Scale => {
+            let band = |factor| vec![MachFactor { mach_low: 0.0, mach_high: f64::INFINITY, factor }];
+            let cost = |factor: f64| -> Result<f64, String> {
+                let (_, predicted) = truing.predict(&band(factor), &targets)?;
+                Ok(targets.iter().zip(predicted.iter())
+                    .map(|(obs, p)| ((p - obs.value()) / obs.value()).powi(2))
+                    .sum())
+            };
+
+            // Golden-section search on the single factor.
+            let ratio = (5f64.sqrt() - 1.0) / 2.0;
+            let (mut lo, mut hi) = (FACTOR_MIN, FACTOR_MAX);
+            let (mut a, mut b) = (hi - ratio * (hi - lo), lo + ratio * (hi - lo));
+            let (mut cost_a, mut cost_b) = (cost(a)?, cost(b)?);
+            for _ in 0..ITERATIONS {
+                if cost_a < cost_b {
+                    hi = b;
+                    (b, cost_b) = (a, cost_a);
+                    a = hi - ratio * (hi - lo);
+                    cost_a = cost(a)?;
+                } else {
+                    lo = a;
+                    (a, cost_a) = (b, cost_b);
+                    b = lo + ratio * (hi - lo);
+                    cost_b = cost(b)?;
+                }
+            }
+            let factor = 0.5 * (lo + hi);
+
+            // A factor pinned to a bound matches nothing; name the
+            // observation furthest from the prediction.
+            if factor / FACTOR_MIN - 1.0 < 1e-6 || 1.0 - factor / FACTOR_MAX < 1e-6 {
+                let (_, predicted) = truing.predict(&band(factor), &targets)?;
+                let worst = targets.iter().zip(predicted.iter())
+                    .max_by(|(a, pa), (b, pb)| {
+                        ((*pa - a.value()) / a.value()).abs().total_cmp(&((*pb - b.value()) / b.value()).abs())
+                    })
+                    .map_or(targets[0], |(obs, _)| *obs);
+                return Err(format!(
+                    "Observed {} cannot be matched by a CD0 factor between {} and {}",
+                    worst.describe(),
+                    FACTOR_MIN,
+                    FACTOR_MAX
+                ));
+            }
+            band(factor)
+        }
+        TruingMode::MachDependent => {
+            let mut factors: Vec<MachFactor> = Vec::new();
+            let mut mach_high = f64::INFINITY;
+            for target in &targets {
+                let previous = factors.last().map_or(1.0, |band| band.factor);
+                let trial = |factor: f64, mach_low: f64| {
+                    let mut bands = factors.clone();
+                    bands.push(MachFactor { mach_low, mach_high, factor });
+                    bands
+                };
+
+                // The band ends at the Mach number reached at this observation.
+                let probe = trial(previous, 0.0);
+                let drag = apply_factors(&truing.base, &probe);
+                let velocity = truing.muzzle_velocity(&drag)?;
+                let samples = truing.flight(drag, velocity)?;
+                let mach_low = Truing::sample_at(&samples, target.range())?.velocity / sound;
+                if mach_low > mach_high {
+                    return Err(format!(
+                        "Observed {} needs a higher Mach number than the observation before it; the observations contradict each other",
+                        target.describe()
+                    ));
+                }
+
+                let error = |factor: f64| -> Result<f64, String> {
+                    let (_, predicted) = truing.predict(&trial(factor, mach_low), &[*target])?;
+                    Ok(predicted[0] - target.value())
+                };
+                let (mut lo, mut hi) = (FACTOR_MIN, FACTOR_MAX);
+                let (e_lo, e_hi) = (error(lo)?, error(hi)?);
+                if e_lo.signum() == e_hi.signum() {
+                    return Err(format!(
+                        "Observed {} cannot be matched by a CD0 factor between {} and {}",
+                        target.describe(),
+                        FACTOR_MIN,
+                        FACTOR_MAX
+                    ));
+                }
+                for _ in 0..ITERATIONS {
+                    let mid = 0.5 * (lo + hi);
+                    if error(mid)?.signum() == e_lo.signum() {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                let factor = 0.5 * (lo + hi);
+
+                factors.push(MachFactor { mach_low, mach_high, factor });
+                mach_high = mach_low;
+            }
+            factors
+        }
+    };
+
+    let (muzzle_velocity, predicted) = truing.predict(&factors, &targets)?;
+    let fits = targets.iter().zip(predicted.iter())
+        .map(|(obs, &p)| ObservationFit {
+            observation: *obs,
+            predicted: p,
+            residual: p - obs.value(),
+        })
+        .collect();
+
+    let drag = apply_factors(&truing.base, &factors);
+    let samples = truing.flight(drag.clone(), muzzle_velocity)?;
+    let machs: Vec<(f64, f64)> = samples.iter()
+        .map(|s| {
+            let mach = s.velocity / sound;
+            (mach, trajectory::cd0_at(&drag, mach))
+        })
+        .collect();
+
+    Ok(TruingResult {
+        muzzle_velocity,
+        factors,
+        bc_g1: standard_drag::ballistic_coefficient(DragModel::G1, params.mass, input.ref_diameter, &machs),
+        bc_g7: standard_drag::ballistic_coefficient(DragModel::G7, params.mass, input.ref_diameter, &machs),
+        drag,
+        fits,
+    })
+}
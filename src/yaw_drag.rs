@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+use crate::aero::AeroCoefficients;
+use crate::trajectory::{self, TrajectoryInput};
+use crate::{DragCoefficients, ProjectileInput};
+
+/// Zero-yaw and yaw drag coefficients at one Mach number.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YawDragCoefficients {
+    pub mach: f64,
+    pub cd0: f64,
+    pub cd_delta2: f64,
+}
+
+/// Linear-theory epicyclic yaw motion at the muzzle, started by the first
+/// maximum yaw `first_max_yaw` (degrees). Frequencies and damping exponents
+/// are per caliber of travel; negative damping exponents decay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YawHistory {
+    pub mach: f64,
+    pub first_max_yaw: f64,
+    pub fast_frequency: f64,
+    pub slow_frequency: f64,
+    pub fast_damping: f64,
+    pub slow_damping: f64,
+    pub distance: f64,
+    pub mean_squared_yaw: f64,
+    pub cd0: f64,
+    pub average_cd: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YawHistoryRequest {
+    pub trajectory: TrajectoryInput,
+    pub first_max_yaw: f64,
+    pub distance: f64,
+}
+
+impl ProjectileInput {
+    /// Yaw drag coefficient CDd2 for each Mach number of a drag table.
+    pub fn yaw_drag_coefficients(&self, drag: &[DragCoefficients]) -> Vec<YawDragCoefficients> {
+        self.aero_coefficients(drag, self.effective_cg())
+            .into_iter()
+            .map(|aero| YawDragCoefficients {
+                mach: aero.mach,
+                cd0: aero.cd0,
+                cd_delta2: aero.cd_delta2,
+            })
+            .collect()
+    }
+
+    /// Total drag coefficient at `mach` and a total angle of attack of
+    /// `alpha` degrees: CD = CD0 + CDd2 * sin^2(alpha).
+    pub fn drag_at_angle_of_attack(&self, drag: &[DragCoefficients], mach: f64, alpha: f64) -> f64 {
+        let table = self.yaw_drag_coefficients(drag);
+        let cd0 = trajectory::cd0_at(drag, mach);
+        let cd_delta2 = trajectory::interpolate(&table, mach, |row| row.mach, |row| row.cd_delta2);
+        let delta = alpha.to_radians().sin();
+        cd0 + cd_delta2 * delta * delta
+    }
+
+    /// Average drag coefficient over the first `distance` meters of flight
+    /// for a muzzle-exit yaw disturbance. The fast and slow arms of the
+    /// tricyclic motion start with equal amplitudes (a pure initial yaw
+    /// rate), so each is half the first maximum yaw; their frequencies and
+    /// damping come from McCoy's linear theory with the geometry-derived
+    /// coefficients at the muzzle Mach number. The squared yaw is averaged
+    /// over distance, the cross terms averaging out over many cycles.
+    pub fn yaw_history(
+        &self,
+        params: &TrajectoryInput,
+        first_max_yaw: f64,
+        distance: f64,
+    ) -> Result<YawHistory, String> {
+        if params.mass <= 0.0 || params.muzzle_velocity <= 0.0 || params.twist == 0.0 {
+            return Err("Mass, muzzle velocity and twist must be given".to_string());
+        }
+        if distance <= 0.0 {
+            return Err("Averaging distance must be positive".to_string());
+        }
+
+        let drag = self.calculate_drag_coefficients();
+        let mach = params.muzzle_velocity / params.atmosphere.speed_of_sound();
+        let aero = self.aero_coefficients(&drag, self.effective_cg());
+        let at = |value: fn(&AeroCoefficients) -> f64| {
+            trajectory::interpolate(&aero, mach, |a| a.mach, value)
+        };
+
+        let mass = params.mass / 1000.0;
+        let d = self.ref_diameter / 1000.0;
+        let properties = self.mass_properties(mass);
+        let ka2 = properties.axial_inertia / (mass * d * d);
+        let kt2 = properties.transverse_inertia / (mass * d * d);
+        let area = 0.25 * std::f64::consts::PI * d * d;
+        let c = params.atmosphere.density() * area * d / (2.0 * mass);
+
+        let cd0 = at(|a| a.cd0);
+        let cl_alpha = at(|a| a.cl_alpha);
+        let cm_alpha = at(|a| a.cm_alpha);
+        let cm_q = at(|a| a.cm_q);
+        let cd_delta2 = at(|a| a.cd_delta2);
+
+        // Spin per caliber of travel: one turn per `twist` calibers.
+        let p = (ka2 / kt2) * 2.0 * std::f64::consts::PI / params.twist.abs();
+        let m = c * cm_alpha / kt2;
+        let h = c * (cl_alpha - cd0 - cm_q / kt2);
+        let t = c * cl_alpha;
+        let discriminant = p * p - 4.0 * m;
+        if discriminant <= 0.0 {
+            return Err("Projectile is gyroscopically unstable at the muzzle".to_string());
+        }
+        let root = discriminant.sqrt();
+        let fast_frequency = 0.5 * (p + root);
+        let slow_frequency = 0.5 * (p - root);
+        let fast_damping = -0.5 * (h - p * (2.0 * t - h) / root);
+        let slow_damping = -0.5 * (h + p * (2.0 * t - h) / root);
+
+        let s = distance / d;
+        let arm = 0.5 * first_max_yaw.to_radians().sin();
+        let mean = |lambda: f64| {
+            if (lambda * s).abs() < 1e-9 {
+                1.0
+            } else {
+                ((2.0 * lambda * s).exp() - 1.0) / (2.0 * lambda * s)
+            }
+        };
+        let mean_squared_yaw = arm * arm * (mean(fast_damping) + mean(slow_damping));
+
+        Ok(YawHistory {
+            mach,
+            first_max_yaw,
+            fast_frequency,
+            slow_frequency,
+            fast_damping,
+            slow_damping,
+            distance,
+            mean_squared_yaw,
+            cd0,
+            average_cd: cd0 + cd_delta2 * mean_squared_yaw,
+        })
+    }
+}